@@ -1,4 +1,6 @@
+use crate::types::CCsrMatrix;
 use crate::types::CNgramArray;
+use crate::types::CRangeArray;
 use crate::types::CTokenArray;
 use crate::Result;
 use ffi_utils::{take_back_c_string, take_back_c_string_array, CStringArray, RawPointerConverter};
@@ -22,3 +24,13 @@ pub fn destroy_ngram_array_c(ptr: *mut CNgramArray) -> Result<()> {
     let _ = unsafe { CNgramArray::from_raw_pointer(ptr) };
     Ok(())
 }
+
+pub fn destroy_csr_matrix_c(ptr: *mut CCsrMatrix) -> Result<()> {
+    let _ = unsafe { CCsrMatrix::from_raw_pointer(ptr) };
+    Ok(())
+}
+
+pub fn destroy_range_array_c(ptr: *mut CRangeArray) -> Result<()> {
+    let _ = unsafe { CRangeArray::from_raw_pointer(ptr) };
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use crate::Result;
+use snips_nlu_utils::hashing::*;
+use std::ffi::CStr;
+
+pub fn fnv1a_64_c(input: *const ::libc::c_char, result: *mut ::libc::c_ulonglong) -> Result<()> {
+    let str_input = unsafe { CStr::from_ptr(input) }.to_str()?;
+    let hash = fnv1a_64(str_input);
+    unsafe { *result = hash as ::libc::c_ulonglong };
+    Ok(())
+}
+
+pub fn fnv1a_64_with_seed_c(
+    input: *const ::libc::c_char,
+    seed: ::libc::c_ulonglong,
+    result: *mut ::libc::c_ulonglong,
+) -> Result<()> {
+    let str_input = unsafe { CStr::from_ptr(input) }.to_str()?;
+    let hash = fnv1a_64_with_seed(str_input, seed);
+    unsafe { *result = hash as ::libc::c_ulonglong };
+    Ok(())
+}
+
+pub fn murmurhash3_32_c(
+    input: *const ::libc::c_char,
+    seed: ::libc::c_uint,
+    result: *mut ::libc::c_int,
+) -> Result<()> {
+    let str_input = unsafe { CStr::from_ptr(input) }.to_str()?;
+    let hash = murmurhash3_32(str_input, seed);
+    unsafe { *result = hash as ::libc::c_int };
+    Ok(())
+}
@@ -1,7 +1,11 @@
 mod destroy;
+mod hashing;
+mod phonetic;
+mod range;
 mod string;
 mod token;
 mod types;
+mod vectorizer;
 
 use crate::types::CNgramArray;
 use ffi_utils::*;
@@ -36,6 +40,18 @@ pub extern "C" fn snips_nlu_utils_destroy_token_array(
     wrap!(destroy::destroy_token_array_c(ptr))
 }
 
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_destroy_range_array(
+    ptr: *mut types::CRangeArray,
+) -> SNIPS_RESULT {
+    wrap!(destroy::destroy_range_array_c(ptr))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_destroy_csr_matrix(ptr: *mut types::CCsrMatrix) -> SNIPS_RESULT {
+    wrap!(destroy::destroy_csr_matrix_c(ptr))
+}
+
 #[no_mangle]
 pub extern "C" fn snips_nlu_utils_remove_diacritics(
     input: *const ::libc::c_char,
@@ -52,6 +68,50 @@ pub extern "C" fn snips_nlu_utils_normalize(
     wrap!(string::normalize_c(input, result))
 }
 
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_ascii_fold(
+    input: *const ::libc::c_char,
+    result: *mut *const ::libc::c_char,
+) -> SNIPS_RESULT {
+    wrap!(string::ascii_fold_c(input, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_convert_to_char_index(
+    input: *const ::libc::c_char,
+    byte_index: ::libc::c_uint,
+    result: *mut ::libc::c_uint,
+) -> SNIPS_RESULT {
+    wrap!(string::convert_to_char_index_c(input, byte_index, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_convert_to_byte_index(
+    input: *const ::libc::c_char,
+    char_index: ::libc::c_uint,
+    result: *mut ::libc::c_uint,
+) -> SNIPS_RESULT {
+    wrap!(string::convert_to_byte_index_c(input, char_index, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_convert_to_char_range(
+    input: *const ::libc::c_char,
+    byte_range: *const types::CRange,
+    result: *mut types::CRange,
+) -> SNIPS_RESULT {
+    wrap!(string::convert_to_char_range_c(input, byte_range, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_convert_to_byte_range(
+    input: *const ::libc::c_char,
+    char_range: *const types::CRange,
+    result: *mut types::CRange,
+) -> SNIPS_RESULT {
+    wrap!(string::convert_to_byte_range_c(input, char_range, result))
+}
+
 #[no_mangle]
 pub extern "C" fn snips_nlu_utils_get_shape(
     input: *const ::libc::c_char,
@@ -60,6 +120,31 @@ pub extern "C" fn snips_nlu_utils_get_shape(
     wrap!(string::get_shape_c(input, result))
 }
 
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_word_shape(
+    input: *const ::libc::c_char,
+    result: *mut *const ::libc::c_char,
+) -> SNIPS_RESULT {
+    wrap!(string::word_shape_c(input, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_detailed_shape(
+    input: *const ::libc::c_char,
+    result: *mut *const ::libc::c_char,
+) -> SNIPS_RESULT {
+    wrap!(string::detailed_shape_c(input, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_compressed_shape(
+    input: *const ::libc::c_char,
+    max_run_length: ::libc::c_uint,
+    result: *mut *const ::libc::c_char,
+) -> SNIPS_RESULT {
+    wrap!(string::compressed_shape_c(input, max_run_length, result))
+}
+
 #[no_mangle]
 pub extern "C" fn snips_nlu_utils_hash_str_to_i32(
     input: *const ::libc::c_char,
@@ -68,6 +153,129 @@ pub extern "C" fn snips_nlu_utils_hash_str_to_i32(
     wrap!(string::hash_str_to_i32_c(input, result))
 }
 
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_fnv1a_64(
+    input: *const ::libc::c_char,
+    result: *mut ::libc::c_ulonglong,
+) -> SNIPS_RESULT {
+    wrap!(hashing::fnv1a_64_c(input, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_fnv1a_64_with_seed(
+    input: *const ::libc::c_char,
+    seed: ::libc::c_ulonglong,
+    result: *mut ::libc::c_ulonglong,
+) -> SNIPS_RESULT {
+    wrap!(hashing::fnv1a_64_with_seed_c(input, seed, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_murmurhash3_32(
+    input: *const ::libc::c_char,
+    seed: ::libc::c_uint,
+    result: *mut ::libc::c_int,
+) -> SNIPS_RESULT {
+    wrap!(hashing::murmurhash3_32_c(input, seed, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_levenshtein_distance(
+    lhs: *const ::libc::c_char,
+    rhs: *const ::libc::c_char,
+    normalize_input: ::libc::c_uchar,
+    result: *mut ::libc::c_uint,
+) -> SNIPS_RESULT {
+    wrap!(string::levenshtein_distance_c(lhs, rhs, normalize_input, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_levenshtein_distance_bounded(
+    lhs: *const ::libc::c_char,
+    rhs: *const ::libc::c_char,
+    max_distance: ::libc::c_uint,
+    normalize_input: ::libc::c_uchar,
+    result: *mut ::libc::c_int,
+) -> SNIPS_RESULT {
+    wrap!(string::levenshtein_distance_bounded_c(
+        lhs,
+        rhs,
+        max_distance,
+        normalize_input,
+        result
+    ))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_damerau_levenshtein_distance(
+    lhs: *const ::libc::c_char,
+    rhs: *const ::libc::c_char,
+    normalize_input: ::libc::c_uchar,
+    result: *mut ::libc::c_uint,
+) -> SNIPS_RESULT {
+    wrap!(string::damerau_levenshtein_distance_c(
+        lhs,
+        rhs,
+        normalize_input,
+        result
+    ))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_damerau_levenshtein_distance_bounded(
+    lhs: *const ::libc::c_char,
+    rhs: *const ::libc::c_char,
+    max_distance: ::libc::c_uint,
+    normalize_input: ::libc::c_uchar,
+    result: *mut ::libc::c_int,
+) -> SNIPS_RESULT {
+    wrap!(string::damerau_levenshtein_distance_bounded_c(
+        lhs,
+        rhs,
+        max_distance,
+        normalize_input,
+        result
+    ))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_levenshtein_similarity(
+    lhs: *const ::libc::c_char,
+    rhs: *const ::libc::c_char,
+    normalize_input: ::libc::c_uchar,
+    result: *mut ::libc::c_double,
+) -> SNIPS_RESULT {
+    wrap!(string::levenshtein_similarity_c(lhs, rhs, normalize_input, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_jaro_winkler_similarity(
+    lhs: *const ::libc::c_char,
+    rhs: *const ::libc::c_char,
+    normalize_input: ::libc::c_uchar,
+    result: *mut ::libc::c_double,
+) -> SNIPS_RESULT {
+    wrap!(string::jaro_winkler_similarity_c(lhs, rhs, normalize_input, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_double_metaphone(
+    input: *const ::libc::c_char,
+    primary: *mut *const ::libc::c_char,
+    alternate: *mut *const ::libc::c_char,
+) -> SNIPS_RESULT {
+    wrap!(phonetic::double_metaphone_c(input, primary, alternate))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_phonetic_key(
+    input: *const ::libc::c_char,
+    language: *const ::libc::c_char,
+    result: *mut *const ::libc::c_char,
+) -> SNIPS_RESULT {
+    wrap!(phonetic::phonetic_key_c(input, language, result))
+}
+
 #[no_mangle]
 pub extern "C" fn snips_nlu_utils_tokenize(
     input: *const ::libc::c_char,
@@ -94,3 +302,79 @@ pub extern "C" fn snips_nlu_utils_compute_all_ngrams(
 ) -> SNIPS_RESULT {
     wrap!(token::compute_all_ngrams_c(tokens, max_ngram_size, result))
 }
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_hashing_vectorize(
+    inputs: *const ::ffi_utils::CStringArray,
+    language: *const libc::c_char,
+    nb_buckets: libc::c_uint,
+    max_ngram_size: libc::c_uint,
+    signed: libc::c_uchar,
+    l2_normalize: libc::c_uchar,
+    normalize_tokens: libc::c_uchar,
+    result: *mut *const types::CCsrMatrix,
+) -> SNIPS_RESULT {
+    wrap!(vectorizer::hashing_vectorize_c(
+        inputs,
+        language,
+        nb_buckets,
+        max_ngram_size,
+        signed,
+        l2_normalize,
+        normalize_tokens,
+        result
+    ))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_range_contains(
+    outer: *const types::CRange,
+    inner: *const types::CRange,
+    result: *mut libc::c_uchar,
+) -> SNIPS_RESULT {
+    wrap!(range::range_contains_c(outer, inner, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_range_distance(
+    range_1: *const types::CRange,
+    range_2: *const types::CRange,
+    result: *mut libc::c_uint,
+) -> SNIPS_RESULT {
+    wrap!(range::range_distance_c(range_1, range_2, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_range_intersection(
+    range_1: *const types::CRange,
+    range_2: *const types::CRange,
+    result: *mut *const types::CRangeArray,
+) -> SNIPS_RESULT {
+    wrap!(range::range_intersection_c(range_1, range_2, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_range_union(
+    range_1: *const types::CRange,
+    range_2: *const types::CRange,
+    result: *mut *const types::CRangeArray,
+) -> SNIPS_RESULT {
+    wrap!(range::range_union_c(range_1, range_2, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_range_subtract(
+    range_1: *const types::CRange,
+    range_2: *const types::CRange,
+    result: *mut *const types::CRangeArray,
+) -> SNIPS_RESULT {
+    wrap!(range::range_subtract_c(range_1, range_2, result))
+}
+
+#[no_mangle]
+pub extern "C" fn snips_nlu_utils_merge_overlapping_ranges(
+    ranges: *const types::CRangeArray,
+    result: *mut *const types::CRangeArray,
+) -> SNIPS_RESULT {
+    wrap!(range::merge_overlapping_ranges_c(ranges, result))
+}
@@ -0,0 +1,38 @@
+use crate::Result;
+use failure::format_err;
+use ffi_utils::convert_to_c_string;
+use snips_nlu_utils::language::Language;
+use snips_nlu_utils::phonetic::*;
+use std::ffi::CStr;
+use std::str::FromStr;
+
+pub fn double_metaphone_c(
+    input: *const ::libc::c_char,
+    primary: *mut *const ::libc::c_char,
+    alternate: *mut *const ::libc::c_char,
+) -> Result<()> {
+    let str_input = unsafe { CStr::from_ptr(input) }.to_str()?;
+    let codes = double_metaphone(str_input);
+    let primary_cs = convert_to_c_string!(codes.primary);
+    let alternate_cs = convert_to_c_string!(codes.alternate);
+    unsafe {
+        *primary = primary_cs;
+        *alternate = alternate_cs;
+    };
+    Ok(())
+}
+
+pub fn phonetic_key_c(
+    input: *const ::libc::c_char,
+    language: *const ::libc::c_char,
+    result: *mut *const ::libc::c_char,
+) -> Result<()> {
+    let str_input = unsafe { CStr::from_ptr(input) }.to_str()?;
+    let str_lang = unsafe { CStr::from_ptr(language) }.to_str()?;
+    let language = Language::from_str(str_lang)?;
+    let key = phonetic_key(str_input, language)
+        .ok_or_else(|| format_err!("No phonetic encoding for language {}", str_lang))?;
+    let cs = convert_to_c_string!(key);
+    unsafe { *result = cs };
+    Ok(())
+}
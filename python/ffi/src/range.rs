@@ -0,0 +1,73 @@
+use crate::types::{CRange, CRangeArray};
+use crate::Result;
+use ffi_utils::{CReprOf, RawPointerConverter};
+use snips_nlu_utils::range::*;
+
+pub fn range_contains_c(
+    outer: *const CRange,
+    inner: *const CRange,
+    result: *mut libc::c_uchar,
+) -> Result<()> {
+    let (outer, inner) = unsafe { ((*outer).to_range(), (*inner).to_range()) };
+    unsafe { *result = contains(&outer, &inner) as libc::c_uchar };
+    Ok(())
+}
+
+pub fn range_distance_c(
+    range_1: *const CRange,
+    range_2: *const CRange,
+    result: *mut libc::c_uint,
+) -> Result<()> {
+    let (range_1, range_2) = unsafe { ((*range_1).to_range(), (*range_2).to_range()) };
+    unsafe { *result = distance(&range_1, &range_2) as libc::c_uint };
+    Ok(())
+}
+
+/// The result array is empty when the ranges do not overlap
+pub fn range_intersection_c(
+    range_1: *const CRange,
+    range_2: *const CRange,
+    result: *mut *const CRangeArray,
+) -> Result<()> {
+    let (range_1, range_2) = unsafe { ((*range_1).to_range(), (*range_2).to_range()) };
+    let ranges = intersection(&range_1, &range_2).into_iter().collect();
+    let c_ranges = CRangeArray::c_repr_of(ranges)?.into_raw_pointer();
+    unsafe { *result = c_ranges };
+    Ok(())
+}
+
+/// The result array is empty when the ranges are neither overlapping nor adjacent
+pub fn range_union_c(
+    range_1: *const CRange,
+    range_2: *const CRange,
+    result: *mut *const CRangeArray,
+) -> Result<()> {
+    let (range_1, range_2) = unsafe { ((*range_1).to_range(), (*range_2).to_range()) };
+    let ranges = union(&range_1, &range_2).into_iter().collect();
+    let c_ranges = CRangeArray::c_repr_of(ranges)?.into_raw_pointer();
+    unsafe { *result = c_ranges };
+    Ok(())
+}
+
+pub fn range_subtract_c(
+    range_1: *const CRange,
+    range_2: *const CRange,
+    result: *mut *const CRangeArray,
+) -> Result<()> {
+    let (range_1, range_2) = unsafe { ((*range_1).to_range(), (*range_2).to_range()) };
+    let ranges = subtract(&range_1, &range_2);
+    let c_ranges = CRangeArray::c_repr_of(ranges)?.into_raw_pointer();
+    unsafe { *result = c_ranges };
+    Ok(())
+}
+
+pub fn merge_overlapping_ranges_c(
+    ranges: *const CRangeArray,
+    result: *mut *const CRangeArray,
+) -> Result<()> {
+    let ranges = unsafe { (*ranges).to_ranges() };
+    let merged = merge_overlapping(&ranges);
+    let c_ranges = CRangeArray::c_repr_of(merged)?.into_raw_pointer();
+    unsafe { *result = c_ranges };
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::types::CRange;
 use crate::Result;
 use ffi_utils::{convert_to_c_string, CReprOf, RawPointerConverter};
 use snips_nlu_utils::string::*;
@@ -22,6 +23,60 @@ pub fn normalize_c(input: *const ::libc::c_char, result: *mut *const ::libc::c_c
     Ok(())
 }
 
+pub fn ascii_fold_c(input: *const ::libc::c_char, result: *mut *const ::libc::c_char) -> Result<()> {
+    let str_input = unsafe { CStr::from_ptr(input) }.to_str()?;
+    let folded_string = ascii_fold(str_input);
+    let cs = convert_to_c_string!(folded_string);
+    unsafe { *result = cs };
+    Ok(())
+}
+
+pub fn convert_to_char_index_c(
+    input: *const ::libc::c_char,
+    byte_index: ::libc::c_uint,
+    result: *mut ::libc::c_uint,
+) -> Result<()> {
+    let str_input = unsafe { CStr::from_ptr(input) }.to_str()?;
+    let char_index = try_convert_to_char_index(str_input, byte_index as usize)?;
+    unsafe { *result = char_index as ::libc::c_uint };
+    Ok(())
+}
+
+pub fn convert_to_byte_index_c(
+    input: *const ::libc::c_char,
+    char_index: ::libc::c_uint,
+    result: *mut ::libc::c_uint,
+) -> Result<()> {
+    let str_input = unsafe { CStr::from_ptr(input) }.to_str()?;
+    let byte_index = try_convert_to_byte_index(str_input, char_index as usize)?;
+    unsafe { *result = byte_index as ::libc::c_uint };
+    Ok(())
+}
+
+pub fn convert_to_char_range_c(
+    input: *const ::libc::c_char,
+    byte_range: *const CRange,
+    result: *mut CRange,
+) -> Result<()> {
+    let str_input = unsafe { CStr::from_ptr(input) }.to_str()?;
+    let byte_range = unsafe { (*byte_range).to_range() };
+    let char_range = try_convert_to_char_range(str_input, &byte_range)?;
+    unsafe { *result = CRange::from_range(char_range) };
+    Ok(())
+}
+
+pub fn convert_to_byte_range_c(
+    input: *const ::libc::c_char,
+    char_range: *const CRange,
+    result: *mut CRange,
+) -> Result<()> {
+    let str_input = unsafe { CStr::from_ptr(input) }.to_str()?;
+    let char_range = unsafe { (*char_range).to_range() };
+    let byte_range = try_convert_to_byte_range(str_input, &char_range)?;
+    unsafe { *result = CRange::from_range(byte_range) };
+    Ok(())
+}
+
 pub fn get_shape_c(input: *const ::libc::c_char, result: *mut *const ::libc::c_char) -> Result<()> {
     let str_input = unsafe { CStr::from_ptr(input) }.to_str()?;
     let shape = get_shape(str_input).to_string();
@@ -30,9 +85,132 @@ pub fn get_shape_c(input: *const ::libc::c_char, result: *mut *const ::libc::c_c
     Ok(())
 }
 
+pub fn word_shape_c(input: *const ::libc::c_char, result: *mut *const ::libc::c_char) -> Result<()> {
+    let str_input = unsafe { CStr::from_ptr(input) }.to_str()?;
+    let shape = word_shape(str_input).as_str().to_string();
+    let cs = convert_to_c_string!(shape);
+    unsafe { *result = cs };
+    Ok(())
+}
+
+pub fn detailed_shape_c(
+    input: *const ::libc::c_char,
+    result: *mut *const ::libc::c_char,
+) -> Result<()> {
+    let str_input = unsafe { CStr::from_ptr(input) }.to_str()?;
+    let shape = detailed_shape(str_input);
+    let cs = convert_to_c_string!(shape);
+    unsafe { *result = cs };
+    Ok(())
+}
+
+pub fn compressed_shape_c(
+    input: *const ::libc::c_char,
+    max_run_length: ::libc::c_uint,
+    result: *mut *const ::libc::c_char,
+) -> Result<()> {
+    let str_input = unsafe { CStr::from_ptr(input) }.to_str()?;
+    let shape = compressed_shape(str_input, max_run_length as usize);
+    let cs = convert_to_c_string!(shape);
+    unsafe { *result = cs };
+    Ok(())
+}
+
 pub fn hash_str_to_i32_c(input: *const ::libc::c_char, result: *mut ::libc::c_int) -> Result<()> {
     let str_input = unsafe { CStr::from_ptr(input) }.to_str()?;
     let hash = hash_str_to_i32(str_input);
     unsafe { *result = hash };
     Ok(())
 }
+
+pub fn levenshtein_distance_c(
+    lhs: *const ::libc::c_char,
+    rhs: *const ::libc::c_char,
+    normalize_input: ::libc::c_uchar,
+    result: *mut ::libc::c_uint,
+) -> Result<()> {
+    let str_lhs = unsafe { CStr::from_ptr(lhs) }.to_str()?;
+    let str_rhs = unsafe { CStr::from_ptr(rhs) }.to_str()?;
+    let distance = levenshtein_distance(str_lhs, str_rhs, normalize_input != 0);
+    unsafe { *result = distance as ::libc::c_uint };
+    Ok(())
+}
+
+/// The result is set to -1 when the distance is greater than `max_distance`
+pub fn levenshtein_distance_bounded_c(
+    lhs: *const ::libc::c_char,
+    rhs: *const ::libc::c_char,
+    max_distance: ::libc::c_uint,
+    normalize_input: ::libc::c_uchar,
+    result: *mut ::libc::c_int,
+) -> Result<()> {
+    let str_lhs = unsafe { CStr::from_ptr(lhs) }.to_str()?;
+    let str_rhs = unsafe { CStr::from_ptr(rhs) }.to_str()?;
+    let distance = levenshtein_distance_bounded(
+        str_lhs,
+        str_rhs,
+        max_distance as usize,
+        normalize_input != 0,
+    );
+    unsafe { *result = distance.map(|d| d as ::libc::c_int).unwrap_or(-1) };
+    Ok(())
+}
+
+pub fn damerau_levenshtein_distance_c(
+    lhs: *const ::libc::c_char,
+    rhs: *const ::libc::c_char,
+    normalize_input: ::libc::c_uchar,
+    result: *mut ::libc::c_uint,
+) -> Result<()> {
+    let str_lhs = unsafe { CStr::from_ptr(lhs) }.to_str()?;
+    let str_rhs = unsafe { CStr::from_ptr(rhs) }.to_str()?;
+    let distance = damerau_levenshtein_distance(str_lhs, str_rhs, normalize_input != 0);
+    unsafe { *result = distance as ::libc::c_uint };
+    Ok(())
+}
+
+/// The result is set to -1 when the distance is greater than `max_distance`
+pub fn damerau_levenshtein_distance_bounded_c(
+    lhs: *const ::libc::c_char,
+    rhs: *const ::libc::c_char,
+    max_distance: ::libc::c_uint,
+    normalize_input: ::libc::c_uchar,
+    result: *mut ::libc::c_int,
+) -> Result<()> {
+    let str_lhs = unsafe { CStr::from_ptr(lhs) }.to_str()?;
+    let str_rhs = unsafe { CStr::from_ptr(rhs) }.to_str()?;
+    let distance = damerau_levenshtein_distance_bounded(
+        str_lhs,
+        str_rhs,
+        max_distance as usize,
+        normalize_input != 0,
+    );
+    unsafe { *result = distance.map(|d| d as ::libc::c_int).unwrap_or(-1) };
+    Ok(())
+}
+
+pub fn levenshtein_similarity_c(
+    lhs: *const ::libc::c_char,
+    rhs: *const ::libc::c_char,
+    normalize_input: ::libc::c_uchar,
+    result: *mut ::libc::c_double,
+) -> Result<()> {
+    let str_lhs = unsafe { CStr::from_ptr(lhs) }.to_str()?;
+    let str_rhs = unsafe { CStr::from_ptr(rhs) }.to_str()?;
+    let similarity = levenshtein_similarity(str_lhs, str_rhs, normalize_input != 0);
+    unsafe { *result = similarity };
+    Ok(())
+}
+
+pub fn jaro_winkler_similarity_c(
+    lhs: *const ::libc::c_char,
+    rhs: *const ::libc::c_char,
+    normalize_input: ::libc::c_uchar,
+    result: *mut ::libc::c_double,
+) -> Result<()> {
+    let str_lhs = unsafe { CStr::from_ptr(lhs) }.to_str()?;
+    let str_rhs = unsafe { CStr::from_ptr(rhs) }.to_str()?;
+    let similarity = jaro_winkler_similarity(str_lhs, str_rhs, normalize_input != 0);
+    unsafe { *result = similarity };
+    Ok(())
+}
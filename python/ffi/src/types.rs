@@ -1,6 +1,9 @@
+use std::ops::Range;
+
 use failure::{Error, ResultExt};
 use ffi_utils::{convert_to_c_string, take_back_c_string, CReprOf, RawPointerConverter};
 use snips_nlu_utils::token::{Ngram, Token};
+use snips_nlu_utils::vectorizer::CsrMatrix;
 
 #[repr(C)]
 #[derive(Debug)]
@@ -138,3 +141,136 @@ impl Drop for CNgramArray {
         };
     }
 }
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct CCsrMatrix {
+    pub indptr: *const libc::c_uint,
+    pub nb_rows: libc::c_uint,
+    pub indices: *const libc::c_uint,
+    pub data: *const libc::c_double,
+    pub nb_values: libc::c_uint,
+    pub nb_columns: libc::c_uint,
+}
+
+impl CReprOf<CsrMatrix> for CCsrMatrix {
+    fn c_repr_of(input: CsrMatrix) -> Result<Self, Error> {
+        let nb_rows = input.nb_rows() as libc::c_uint;
+        let nb_values = input.data.len() as libc::c_uint;
+        let nb_columns = input.nb_columns as libc::c_uint;
+        let indptr = Box::into_raw(
+            input
+                .indptr
+                .into_iter()
+                .map(|index| index as libc::c_uint)
+                .collect::<Vec<_>>()
+                .into_boxed_slice(),
+        ) as *const libc::c_uint;
+        let indices = Box::into_raw(
+            input
+                .indices
+                .into_iter()
+                .map(|index| index as libc::c_uint)
+                .collect::<Vec<_>>()
+                .into_boxed_slice(),
+        ) as *const libc::c_uint;
+        let data = Box::into_raw(input.data.into_boxed_slice()) as *const libc::c_double;
+        Ok(Self {
+            indptr,
+            nb_rows,
+            indices,
+            data,
+            nb_values,
+            nb_columns,
+        })
+    }
+}
+
+impl Drop for CCsrMatrix {
+    fn drop(&mut self) {
+        let _ = unsafe {
+            Box::from_raw(std::slice::from_raw_parts_mut(
+                self.indptr as *mut libc::c_uint,
+                self.nb_rows as usize + 1,
+            ))
+        };
+        let _ = unsafe {
+            Box::from_raw(std::slice::from_raw_parts_mut(
+                self.indices as *mut libc::c_uint,
+                self.nb_values as usize,
+            ))
+        };
+        let _ = unsafe {
+            Box::from_raw(std::slice::from_raw_parts_mut(
+                self.data as *mut libc::c_double,
+                self.nb_values as usize,
+            ))
+        };
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone)]
+pub struct CRange {
+    pub start: libc::c_uint,
+    pub end: libc::c_uint,
+}
+
+impl CRange {
+    pub fn from_range(range: Range<usize>) -> Self {
+        Self {
+            start: range.start as libc::c_uint,
+            end: range.end as libc::c_uint,
+        }
+    }
+
+    pub fn to_range(&self) -> Range<usize> {
+        self.start as usize..self.end as usize
+    }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct CRangeArray {
+    pub data: *const CRange,
+    pub size: libc::c_uint,
+}
+
+impl CRangeArray {
+    /// Convert an array allocated by the caller, without taking its ownership
+    pub unsafe fn to_ranges(&self) -> Vec<Range<usize>> {
+        if self.size == 0 {
+            return vec![];
+        }
+        std::slice::from_raw_parts(self.data, self.size as usize)
+            .iter()
+            .map(CRange::to_range)
+            .collect()
+    }
+}
+
+impl CReprOf<Vec<Range<usize>>> for CRangeArray {
+    fn c_repr_of(input: Vec<Range<usize>>) -> Result<Self, Error> {
+        Ok(Self {
+            size: input.len() as libc::c_uint,
+            data: Box::into_raw(
+                input
+                    .into_iter()
+                    .map(CRange::from_range)
+                    .collect::<Vec<_>>()
+                    .into_boxed_slice(),
+            ) as *const CRange,
+        })
+    }
+}
+
+impl Drop for CRangeArray {
+    fn drop(&mut self) {
+        let _ = unsafe {
+            Box::from_raw(std::slice::from_raw_parts_mut(
+                self.data as *mut CRange,
+                self.size as usize,
+            ))
+        };
+    }
+}
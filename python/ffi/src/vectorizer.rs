@@ -0,0 +1,34 @@
+use crate::types::CCsrMatrix;
+use crate::Result;
+use ffi_utils::{AsRust, CReprOf, CStringArray, RawPointerConverter};
+use snips_nlu_utils::language::Language;
+use snips_nlu_utils::vectorizer::*;
+use std::ffi::CStr;
+use std::str::FromStr;
+
+#[allow(clippy::too_many_arguments)]
+pub fn hashing_vectorize_c(
+    inputs: *const CStringArray,
+    language: *const libc::c_char,
+    nb_buckets: libc::c_uint,
+    max_ngram_size: libc::c_uint,
+    signed: libc::c_uchar,
+    l2_normalize: libc::c_uchar,
+    normalize_tokens: libc::c_uchar,
+    result: *mut *const CCsrMatrix,
+) -> Result<()> {
+    let inputs_vec = unsafe { (*inputs).as_rust()? };
+    let str_lang = unsafe { CStr::from_ptr(language) }.to_str()?;
+    let language = Language::from_str(str_lang)?;
+    let config = HashingVectorizerConfig {
+        nb_buckets: nb_buckets as usize,
+        max_ngram_size: max_ngram_size as usize,
+        signed: signed != 0,
+        l2_normalize: l2_normalize != 0,
+        normalize_tokens: normalize_tokens != 0,
+    };
+    let matrix = HashingVectorizer::new(config, language)?.transform_all(&inputs_vec);
+    let c_matrix = CCsrMatrix::c_repr_of(matrix)?.into_raw_pointer();
+    unsafe { *result = c_matrix };
+    Ok(())
+}
@@ -1,3 +1,11 @@
+from snips_nlu_utils.hashing import fnv1a_64, murmurhash3_32
+from snips_nlu_utils.phonetic import double_metaphone, phonetic_key
 from snips_nlu_utils.string import (
-    get_shape, hash_str, normalize, remove_diacritics)
+    ascii_fold, compressed_shape, convert_to_byte_index, convert_to_byte_range,
+    convert_to_char_index, convert_to_char_range, damerau_levenshtein_distance,
+    damerau_levenshtein_distance_bounded, detailed_shape, get_shape, hash_str,
+    jaro_winkler_similarity, levenshtein_distance,
+    levenshtein_distance_bounded, levenshtein_similarity, normalize,
+    remove_diacritics, word_shape)
 from snips_nlu_utils.token import compute_all_ngrams, tokenize, tokenize_light
+from snips_nlu_utils.vectorizer import hashing_vectorize
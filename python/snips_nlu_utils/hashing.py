@@ -0,0 +1,32 @@
+from _ctypes import byref
+from ctypes import c_int, c_uint, c_ulonglong
+
+from snips_nlu_utils.utils import lib, check_ffi_error
+
+
+def fnv1a_64(string, seed=None):
+    """Returns the 64-bit FNV-1a hash of the string, optionally seeded"""
+    hsh = c_ulonglong()
+    if seed is None:
+        exit_code = lib.snips_nlu_utils_fnv1a_64(
+            string.encode("utf8"), byref(hsh))
+    else:
+        exit_code = lib.snips_nlu_utils_fnv1a_64_with_seed(
+            string.encode("utf8"), c_ulonglong(seed), byref(hsh))
+    check_ffi_error(exit_code,
+                    "Something went wrong when hashing '%s'" % string)
+    return int(hsh.value)
+
+
+def murmurhash3_32(string, seed=0, positive=False):
+    """Returns the MurmurHash3 x86 32-bit hash of the string, with the same
+    values as sklearn.utils.murmurhash3_32"""
+    hsh = c_int()
+    exit_code = lib.snips_nlu_utils_murmurhash3_32(
+        string.encode("utf8"), c_uint(seed), byref(hsh))
+    check_ffi_error(exit_code,
+                    "Something went wrong when hashing '%s'" % string)
+    result = int(hsh.value)
+    if positive and result < 0:
+        result += 2 ** 32
+    return result
@@ -0,0 +1,29 @@
+from _ctypes import byref
+from ctypes import c_char_p, string_at
+
+from snips_nlu_utils.utils import lib, string_pointer, check_ffi_error
+
+
+def double_metaphone(string):
+    """Returns the primary and alternate Double Metaphone codes"""
+    with string_pointer(c_char_p()) as primary_ptr, \
+            string_pointer(c_char_p()) as alternate_ptr:
+        exit_code = lib.snips_nlu_utils_double_metaphone(
+            string.encode("utf8"), byref(primary_ptr), byref(alternate_ptr))
+        check_ffi_error(exit_code,
+                        "Something went wrong when computing double metaphone "
+                        "of '%s'" % string)
+        primary = string_at(primary_ptr).decode("utf8")
+        alternate = string_at(alternate_ptr).decode("utf8")
+        return primary, alternate
+
+
+def phonetic_key(string, language):
+    with string_pointer(c_char_p()) as ptr:
+        exit_code = lib.snips_nlu_utils_phonetic_key(
+            string.encode("utf8"), language.encode("utf8"), byref(ptr))
+        check_ffi_error(exit_code,
+                        "Something went wrong when computing phonetic key of "
+                        "'%s'" % string)
+        result = string_at(ptr).decode("utf8")
+        return result
@@ -0,0 +1,74 @@
+from _ctypes import byref, pointer
+from ctypes import c_ubyte, c_uint
+
+from snips_nlu_utils.utils import (
+    CRange, CRangeArray, check_ffi_error, lib, range_array_pointer)
+
+
+def _c_range(rng):
+    return CRange(rng[0], rng[1])
+
+
+def _range_array_operation(c_function, lhs, rhs, operation_name):
+    with range_array_pointer(pointer(CRangeArray())) as ptr:
+        exit_code = c_function(
+            byref(_c_range(lhs)), byref(_c_range(rhs)), byref(ptr))
+        check_ffi_error(exit_code,
+                        "Something went wrong when computing %s of %s and %s"
+                        % (operation_name, lhs, rhs))
+        return ptr.contents.to_pylist()
+
+
+def contains(outer, inner):
+    """Whether the (start, end) range outer contains the range inner"""
+    result = c_ubyte()
+    exit_code = lib.snips_nlu_utils_range_contains(
+        byref(_c_range(outer)), byref(_c_range(inner)), byref(result))
+    check_ffi_error(exit_code,
+                    "Something went wrong when checking if %s contains %s"
+                    % (outer, inner))
+    return bool(result.value)
+
+
+def distance(lhs, rhs):
+    result = c_uint()
+    exit_code = lib.snips_nlu_utils_range_distance(
+        byref(_c_range(lhs)), byref(_c_range(rhs)), byref(result))
+    check_ffi_error(exit_code,
+                    "Something went wrong when computing distance between %s "
+                    "and %s" % (lhs, rhs))
+    return int(result.value)
+
+
+def intersection(lhs, rhs):
+    """Returns the intersection of the ranges, or None when they do not
+    overlap"""
+    ranges = _range_array_operation(
+        lib.snips_nlu_utils_range_intersection, lhs, rhs, "intersection")
+    return ranges[0] if ranges else None
+
+
+def union(lhs, rhs):
+    """Returns the union of the ranges, or None when they are neither
+    overlapping nor adjacent"""
+    ranges = _range_array_operation(
+        lib.snips_nlu_utils_range_union, lhs, rhs, "union")
+    return ranges[0] if ranges else None
+
+
+def subtract(lhs, rhs):
+    return _range_array_operation(
+        lib.snips_nlu_utils_range_subtract, lhs, rhs, "subtraction")
+
+
+def merge_overlapping(ranges):
+    with range_array_pointer(pointer(CRangeArray())) as ptr:
+        nb_ranges = len(ranges)
+        c_ranges = CRangeArray()
+        c_ranges.data = (CRange * nb_ranges)(*[_c_range(r) for r in ranges])
+        c_ranges.size = c_uint(nb_ranges)
+        exit_code = lib.snips_nlu_utils_merge_overlapping_ranges(
+            byref(c_ranges), byref(ptr))
+        check_ffi_error(exit_code,
+                        "Something went wrong when merging ranges %s" % ranges)
+        return ptr.contents.to_pylist()
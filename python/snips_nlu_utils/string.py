@@ -1,7 +1,8 @@
 from _ctypes import byref
-from ctypes import c_char_p, c_int, string_at
+from ctypes import c_char_p, c_double, c_int, c_uint, string_at
 
-from snips_nlu_utils.utils import lib, string_pointer, check_ffi_error
+from snips_nlu_utils.utils import (
+    CRange, lib, string_pointer, check_ffi_error)
 
 
 def remove_diacritics(string):
@@ -24,6 +25,78 @@ def normalize(string):
         return result
 
 
+def ascii_fold(string):
+    with string_pointer(c_char_p()) as ptr:
+        exit_code = lib.snips_nlu_utils_ascii_fold(
+            string.encode("utf8"), byref(ptr))
+        check_ffi_error(exit_code,
+                        "Something went wrong when folding '%s' to ASCII"
+                        % string)
+        result = string_at(ptr).decode("utf8")
+        return result
+
+
+def convert_to_char_index(string, byte_index):
+    """Converts an index in the utf8 encoding of the string into a char index
+
+    Raises a ValueError when the index is past the end of the string or not on
+    a char boundary
+    """
+    result = c_uint()
+    exit_code = lib.snips_nlu_utils_convert_to_char_index(
+        string.encode("utf8"), c_uint(byte_index), byref(result))
+    check_ffi_error(exit_code,
+                    "Something went wrong when converting byte index %s of "
+                    "'%s'" % (byte_index, string))
+    return int(result.value)
+
+
+def convert_to_byte_index(string, char_index):
+    """Converts a char index into an index in the utf8 encoding of the string
+
+    Raises a ValueError when the index is past the end of the string
+    """
+    result = c_uint()
+    exit_code = lib.snips_nlu_utils_convert_to_byte_index(
+        string.encode("utf8"), c_uint(char_index), byref(result))
+    check_ffi_error(exit_code,
+                    "Something went wrong when converting char index %s of "
+                    "'%s'" % (char_index, string))
+    return int(result.value)
+
+
+def convert_to_char_range(string, byte_range):
+    """Converts a (start, end) range in the utf8 encoding of the string into a
+    char range
+
+    Raises a ValueError when the range is inverted, or when one of its bounds
+    is past the end of the string or not on a char boundary
+    """
+    result = CRange()
+    exit_code = lib.snips_nlu_utils_convert_to_char_range(
+        string.encode("utf8"), byref(CRange(*byte_range)), byref(result))
+    check_ffi_error(exit_code,
+                    "Something went wrong when converting byte range %s of "
+                    "'%s'" % (byte_range, string))
+    return result.start, result.end
+
+
+def convert_to_byte_range(string, char_range):
+    """Converts a (start, end) char range into a range in the utf8 encoding of
+    the string
+
+    Raises a ValueError when the range is inverted, or when one of its bounds
+    is past the end of the string
+    """
+    result = CRange()
+    exit_code = lib.snips_nlu_utils_convert_to_byte_range(
+        string.encode("utf8"), byref(CRange(*char_range)), byref(result))
+    check_ffi_error(exit_code,
+                    "Something went wrong when converting char range %s of "
+                    "'%s'" % (char_range, string))
+    return result.start, result.end
+
+
 def get_shape(string):
     with string_pointer(c_char_p()) as ptr:
         exit_code = lib.snips_nlu_utils_get_shape(
@@ -34,6 +107,39 @@ def get_shape(string):
         return result
 
 
+def word_shape(string):
+    with string_pointer(c_char_p()) as ptr:
+        exit_code = lib.snips_nlu_utils_word_shape(
+            string.encode("utf8"), byref(ptr))
+        check_ffi_error(exit_code,
+                        "Something went wrong when getting word shape of '%s'"
+                        % string)
+        result = string_at(ptr).decode("utf8")
+        return result
+
+
+def detailed_shape(string):
+    with string_pointer(c_char_p()) as ptr:
+        exit_code = lib.snips_nlu_utils_detailed_shape(
+            string.encode("utf8"), byref(ptr))
+        check_ffi_error(exit_code,
+                        "Something went wrong when getting detailed shape of "
+                        "'%s'" % string)
+        result = string_at(ptr).decode("utf8")
+        return result
+
+
+def compressed_shape(string, max_run_length=4):
+    with string_pointer(c_char_p()) as ptr:
+        exit_code = lib.snips_nlu_utils_compressed_shape(
+            string.encode("utf8"), c_uint(max_run_length), byref(ptr))
+        check_ffi_error(exit_code,
+                        "Something went wrong when getting compressed shape "
+                        "of '%s'" % string)
+        result = string_at(ptr).decode("utf8")
+        return result
+
+
 def hash_str(string):
     hsh = c_int()
     exit_code = lib.snips_nlu_utils_hash_str_to_i32(
@@ -43,3 +149,73 @@ def hash_str(string):
                     "Something went wrong when hashing '%s'" % string)
     result = int(hsh.value)
     return result
+
+
+def levenshtein_distance(lhs, rhs, normalize_input=False):
+    distance = c_uint()
+    exit_code = lib.snips_nlu_utils_levenshtein_distance(
+        lhs.encode("utf8"), rhs.encode("utf8"), int(normalize_input),
+        byref(distance))
+    check_ffi_error(exit_code,
+                    "Something went wrong when computing levenshtein distance "
+                    "between '%s' and '%s'" % (lhs, rhs))
+    return int(distance.value)
+
+
+def levenshtein_distance_bounded(lhs, rhs, max_distance,
+                                 normalize_input=False):
+    """Returns None when the distance is greater than max_distance"""
+    distance = c_int()
+    exit_code = lib.snips_nlu_utils_levenshtein_distance_bounded(
+        lhs.encode("utf8"), rhs.encode("utf8"), max_distance,
+        int(normalize_input), byref(distance))
+    check_ffi_error(exit_code,
+                    "Something went wrong when computing levenshtein distance "
+                    "between '%s' and '%s'" % (lhs, rhs))
+    return int(distance.value) if distance.value >= 0 else None
+
+
+def damerau_levenshtein_distance(lhs, rhs, normalize_input=False):
+    distance = c_uint()
+    exit_code = lib.snips_nlu_utils_damerau_levenshtein_distance(
+        lhs.encode("utf8"), rhs.encode("utf8"), int(normalize_input),
+        byref(distance))
+    check_ffi_error(exit_code,
+                    "Something went wrong when computing damerau levenshtein "
+                    "distance between '%s' and '%s'" % (lhs, rhs))
+    return int(distance.value)
+
+
+def damerau_levenshtein_distance_bounded(lhs, rhs, max_distance,
+                                         normalize_input=False):
+    """Returns None when the distance is greater than max_distance"""
+    distance = c_int()
+    exit_code = lib.snips_nlu_utils_damerau_levenshtein_distance_bounded(
+        lhs.encode("utf8"), rhs.encode("utf8"), max_distance,
+        int(normalize_input), byref(distance))
+    check_ffi_error(exit_code,
+                    "Something went wrong when computing damerau levenshtein "
+                    "distance between '%s' and '%s'" % (lhs, rhs))
+    return int(distance.value) if distance.value >= 0 else None
+
+
+def levenshtein_similarity(lhs, rhs, normalize_input=False):
+    similarity = c_double()
+    exit_code = lib.snips_nlu_utils_levenshtein_similarity(
+        lhs.encode("utf8"), rhs.encode("utf8"), int(normalize_input),
+        byref(similarity))
+    check_ffi_error(exit_code,
+                    "Something went wrong when computing levenshtein "
+                    "similarity between '%s' and '%s'" % (lhs, rhs))
+    return float(similarity.value)
+
+
+def jaro_winkler_similarity(lhs, rhs, normalize_input=False):
+    similarity = c_double()
+    exit_code = lib.snips_nlu_utils_jaro_winkler_similarity(
+        lhs.encode("utf8"), rhs.encode("utf8"), int(normalize_input),
+        byref(similarity))
+    check_ffi_error(exit_code,
+                    "Something went wrong when computing jaro winkler "
+                    "similarity between '%s' and '%s'" % (lhs, rhs))
+    return float(similarity.value)
@@ -4,9 +4,17 @@ from __future__ import unicode_literals
 import unittest
 
 from snips_nlu_utils import (
-    get_shape, hash_str, normalize, remove_diacritics, tokenize,
-    tokenize_light)
+    ascii_fold, compressed_shape, convert_to_byte_index, convert_to_byte_range,
+    convert_to_char_index, convert_to_char_range, damerau_levenshtein_distance,
+    damerau_levenshtein_distance_bounded, detailed_shape, double_metaphone,
+    fnv1a_64, get_shape, hash_str, jaro_winkler_similarity,
+    levenshtein_distance, levenshtein_distance_bounded, levenshtein_similarity,
+    murmurhash3_32, normalize, phonetic_key, remove_diacritics, tokenize,
+    tokenize_light, word_shape)
+from snips_nlu_utils.range import (
+    contains, distance, intersection, merge_overlapping, subtract, union)
 from snips_nlu_utils.token import compute_all_ngrams
+from snips_nlu_utils.vectorizer import hashing_vectorize
 
 
 class TestNluUtils(unittest.TestCase):
@@ -71,6 +79,23 @@ class TestNluUtils(unittest.TestCase):
         expected_tokens = ["foo", "bär", "baz"]
         self.assertListEqual(expected_tokens, tokens)
 
+    def test_should_tokenize_new_languages(self):
+        # Given
+        utterances = {
+            "nl": ("Hoeveel kost het?", ["Hoeveel", "kost", "het", "?"]),
+            "pl": ("Zażółć gęślą jaźń", ["Zażółć", "gęślą", "jaźń"]),
+            "ru": ("Привет, мир!", ["Привет", "мир", "!"]),
+            "sv": ("Två smörgåsar", ["Två", "smörgåsar"]),
+            "tr": ("Iğdır'da ılık", ["Iğdır", "da", "ılık"]),
+        }
+
+        for language, (u, expected_tokens) in utterances.items():
+            # When
+            tokens = tokenize_light(u, language)
+
+            # Then
+            self.assertListEqual(expected_tokens, tokens)
+
     def test_should_tokenize_light_empty_string(self):
         self.assertListEqual([], tokenize_light("", "en"))
 
@@ -80,6 +105,9 @@ class TestNluUtils(unittest.TestCase):
     def test_should_normalize(self):
         self.assertEqual("hello", normalize("Hëllo"))
 
+    def test_should_fold_to_ascii(self):
+        self.assertEqual("Royksopp aether", ascii_fold("Røyksopp æther"))
+
     def test_should_compute_all_ngrams(self):
         # Given
         tokens = ["hello", "beautiful", "world", "!"]
@@ -101,12 +129,115 @@ class TestNluUtils(unittest.TestCase):
         ]
         self.assertListEqual(expected_ngrams, ngrams)
 
+    def test_should_convert_indexes(self):
+        # Given
+        string = "aé😀b"
+
+        # Then
+        self.assertEqual(3, convert_to_char_index(string, 7))
+        self.assertEqual(7, convert_to_byte_index(string, 3))
+        self.assertTupleEqual((1, 3), convert_to_char_range(string, (1, 7)))
+        self.assertTupleEqual((1, 8), convert_to_byte_range(string, (1, 4)))
+        with self.assertRaises(ValueError):
+            convert_to_char_index(string, 2)
+        with self.assertRaises(ValueError):
+            convert_to_byte_index(string, 5)
+        with self.assertRaises(ValueError):
+            convert_to_char_range(string, (3, 1))
+
     def test_should_get_shape(self):
         self.assertEqual("xxx", get_shape("hello"))
         self.assertEqual("XXX", get_shape("HELLO"))
         self.assertEqual("Xxx", get_shape("Hello"))
         self.assertEqual("xX", get_shape("hEllo"))
 
+    def test_should_get_word_shapes(self):
+        self.assertEqual("xd", word_shape("iPhone11"))
+        self.assertEqual("ddd", word_shape("2019"))
+        self.assertEqual("", word_shape(""))
+        self.assertEqual("dd:dd", detailed_shape("10:30"))
+        self.assertEqual("Xxxxx", compressed_shape("Applesauce"))
+        self.assertEqual("Xx", compressed_shape("Applesauce", 1))
+
     def test_should_hash_str(self):
         self.assertEqual(-2136294133, hash_str("hello"))
         self.assertEqual(1930201843, hash_str("world"))
+
+    def test_should_compute_fnv1a_64(self):
+        self.assertEqual(0x85944171f73967e8, fnv1a_64("foobar"))
+        self.assertNotEqual(fnv1a_64("foobar", seed=1),
+                            fnv1a_64("foobar", seed=2))
+
+    def test_should_compute_murmurhash3_32(self):
+        self.assertEqual(-156908512, murmurhash3_32("foo"))
+        self.assertEqual(4138058784, murmurhash3_32("foo", positive=True))
+
+    def test_should_hashing_vectorize(self):
+        # When
+        data, indices, indptr = hashing_vectorize(
+            ["Foo bar foo", ""], "en", nb_buckets=16, l2_normalize=False)
+
+        # Then
+        self.assertListEqual([-2.0, 1.0], data)
+        self.assertListEqual([0, 13], indices)
+        self.assertListEqual([0, 2, 2], indptr)
+
+    def test_should_hashing_vectorize_turkish(self):
+        # When
+        data, indices, indptr = hashing_vectorize(
+            ["Iğdır ılık", "ılık"], "tr")
+
+        # Then
+        self.assertEqual(3, len(data))
+        self.assertEqual(indices[1], indices[2])
+        self.assertListEqual([0, 2, 3], indptr)
+
+    def test_hashing_vectorize_should_fail_without_buckets(self):
+        with self.assertRaises(ValueError):
+            hashing_vectorize(["foo"], "en", nb_buckets=0)
+
+    def test_should_compute_range_operations(self):
+        self.assertTrue(contains((3, 8), (4, 8)))
+        self.assertEqual(2, distance((3, 6), (8, 9)))
+        self.assertEqual((4, 6), intersection((3, 6), (4, 7)))
+        self.assertIsNone(intersection((3, 6), (6, 7)))
+        self.assertEqual((3, 7), union((3, 6), (6, 7)))
+        self.assertListEqual([(0, 3), (5, 8)], subtract((0, 8), (3, 5)))
+        self.assertListEqual(
+            [(1, 4), (4, 9)],
+            merge_overlapping([(5, 9), (1, 3), (2, 4), (4, 6)]))
+
+    def test_should_compute_levenshtein_distance(self):
+        self.assertEqual(1, levenshtein_distance("spottify", "spotify"))
+        self.assertEqual(0, levenshtein_distance("Spötify", "spotify",
+                                                 normalize_input=True))
+        self.assertEqual(
+            3, levenshtein_distance_bounded("kitten", "sitting", 3))
+        self.assertIsNone(levenshtein_distance_bounded("kitten", "sitting", 2))
+
+    def test_should_compute_damerau_levenshtein_distance(self):
+        self.assertEqual(1, damerau_levenshtein_distance("spotfiy", "spotify"))
+        self.assertEqual(
+            1, damerau_levenshtein_distance_bounded("spotfiy", "spotify", 1))
+        self.assertIsNone(
+            damerau_levenshtein_distance_bounded("abcd", "badc", 1))
+
+    def test_should_compute_similarities(self):
+        self.assertEqual(0.5, levenshtein_similarity("ab", "ac"))
+        self.assertAlmostEqual(
+            0.9611, jaro_winkler_similarity("martha", "marhta"), places=4)
+        self.assertEqual(
+            1.0, jaro_winkler_similarity("Spotify", "spötify",
+                                         normalize_input=True))
+
+    def test_should_compute_double_metaphone(self):
+        self.assertTupleEqual(("XMT", "SMT"), double_metaphone("Schmidt"))
+
+    def test_should_compute_phonetic_key(self):
+        self.assertEqual("SM0", phonetic_key("Smith", "en"))
+        self.assertEqual("67", phonetic_key("Maier", "de"))
+        self.assertEqual("SESILIA", phonetic_key("Cecilia", "es"))
+        with self.assertRaises(ValueError):
+            phonetic_key("Tanaka", "ja")
+        with self.assertRaises(ValueError):
+            phonetic_key("Yılmaz", "tr")
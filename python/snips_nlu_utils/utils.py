@@ -1,6 +1,6 @@
 from _ctypes import byref, Structure, POINTER
 from contextlib import contextmanager
-from ctypes import c_char_p, cdll, string_at, c_int
+from ctypes import c_char_p, c_double, cdll, string_at, c_int, c_uint
 from pathlib import Path
 
 PACKAGE_PATH = Path(__file__).absolute().parent
@@ -77,6 +77,41 @@ class CNgramArray(Structure):
         return [self.data[i].to_pytoken() for i in range(self.size)]
 
 
+class CRange(Structure):
+    _fields_ = [
+        ("start", c_uint),
+        ("end", c_uint)
+    ]
+
+
+class CRangeArray(Structure):
+    _fields_ = [
+        ("data", POINTER(CRange)),
+        ("size", c_uint)
+    ]
+
+    def to_pylist(self):
+        return [(self.data[i].start, self.data[i].end)
+                for i in range(self.size)]
+
+
+class CCsrMatrix(Structure):
+    _fields_ = [
+        ("indptr", POINTER(c_uint)),
+        ("nb_rows", c_uint),
+        ("indices", POINTER(c_uint)),
+        ("data", POINTER(c_double)),
+        ("nb_values", c_uint),
+        ("nb_columns", c_uint),
+    ]
+
+    def to_pytuple(self):
+        data = [self.data[i] for i in range(self.nb_values)]
+        indices = [self.indices[i] for i in range(self.nb_values)]
+        indptr = [self.indptr[i] for i in range(self.nb_rows + 1)]
+        return data, indices, indptr
+
+
 @contextmanager
 def string_pointer(ptr):
     try:
@@ -113,6 +148,24 @@ def ngram_array_pointer(ptr):
             lib.snips_nlu_utils_destroy_ngram_array(ptr)
 
 
+@contextmanager
+def range_array_pointer(ptr):
+    try:
+        yield ptr
+    finally:
+        if ptr and ptr.contents.data:
+            lib.snips_nlu_utils_destroy_range_array(ptr)
+
+
+@contextmanager
+def csr_matrix_pointer(ptr):
+    try:
+        yield ptr
+    finally:
+        if ptr and ptr.contents.indptr:
+            lib.snips_nlu_utils_destroy_csr_matrix(ptr)
+
+
 def check_ffi_error(exit_code, error_context_msg):
     if exit_code != 0:
         with string_pointer(c_char_p()) as ptr:
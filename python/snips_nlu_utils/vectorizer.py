@@ -0,0 +1,28 @@
+from _ctypes import byref, pointer
+from ctypes import c_char_p
+
+from snips_nlu_utils.utils import (
+    CCsrMatrix, CStringArray, check_ffi_error, csr_matrix_pointer, lib)
+
+
+def hashing_vectorize(utterances, language, nb_buckets=2 ** 20,
+                      max_ngram_size=1, signed=True, l2_normalize=True,
+                      normalize_tokens=True):
+    """Vectorizes the utterances by hashing their ngrams
+
+    Returns a (data, indices, indptr) tuple which can be passed to
+    scipy.sparse.csr_matrix, with a shape of (len(utterances), nb_buckets)
+    """
+    with csr_matrix_pointer(pointer(CCsrMatrix())) as ptr:
+        nb_utterances = len(utterances)
+        c_utterances = CStringArray()
+        c_utterances.data = (c_char_p * nb_utterances)(
+            *[utterance.encode("utf8") for utterance in utterances])
+        c_utterances.size = nb_utterances
+        exit_code = lib.snips_nlu_utils_hashing_vectorize(
+            byref(c_utterances), language.encode("utf8"), nb_buckets,
+            max_ngram_size, signed, l2_normalize, normalize_tokens,
+            byref(ptr))
+        check_ffi_error(exit_code,
+                        "Something went wrong when vectorizing utterances")
+        return ptr.contents.to_pytuple()
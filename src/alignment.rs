@@ -0,0 +1,117 @@
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Segment {
+    original_chars: Range<usize>,
+    original_bytes: Range<usize>,
+    normalized_chars: Range<usize>,
+    normalized_bytes: Range<usize>,
+}
+
+/// Alignment between an original string and a normalized version of it
+///
+/// The alignment is made of consecutive segments, each of them mapping a group of original chars
+/// to the normalized chars they produced. A segment can expand chars (e.g. "ß" becoming "ss"),
+/// shrink them (e.g. "e" followed by a combining accent becoming "e"), or delete them (e.g.
+/// trimmed whitespaces).
+///
+/// Conversions are done in O(log n), and ranges falling in the middle of a segment are extended
+/// to the whole segment.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Alignment {
+    segments: Vec<Segment>,
+}
+
+impl Alignment {
+    /// Append a segment mapping the original string slice to the normalized one
+    pub fn push(&mut self, original: &str, normalized: &str) {
+        let (original_char, original_byte, normalized_char, normalized_byte) = self
+            .segments
+            .last()
+            .map(|s| {
+                (
+                    s.original_chars.end,
+                    s.original_bytes.end,
+                    s.normalized_chars.end,
+                    s.normalized_bytes.end,
+                )
+            })
+            .unwrap_or((0, 0, 0, 0));
+        self.segments.push(Segment {
+            original_chars: original_char..original_char + original.chars().count(),
+            original_bytes: original_byte..original_byte + original.len(),
+            normalized_chars: normalized_char..normalized_char + normalized.chars().count(),
+            normalized_bytes: normalized_byte..normalized_byte + normalized.len(),
+        });
+    }
+
+    /// Convert a char range of the normalized string into a char range of the original string
+    pub fn original_char_range(&self, normalized_range: &Range<usize>) -> Range<usize> {
+        self.convert(normalized_range, |s| &s.normalized_chars, |s| &s.original_chars)
+    }
+
+    /// Convert a byte range of the normalized string into a byte range of the original string
+    pub fn original_byte_range(&self, normalized_range: &Range<usize>) -> Range<usize> {
+        self.convert(normalized_range, |s| &s.normalized_bytes, |s| &s.original_bytes)
+    }
+
+    /// Convert a char range of the original string into a char range of the normalized string
+    pub fn normalized_char_range(&self, original_range: &Range<usize>) -> Range<usize> {
+        self.convert(original_range, |s| &s.original_chars, |s| &s.normalized_chars)
+    }
+
+    /// Convert a byte range of the original string into a byte range of the normalized string
+    pub fn normalized_byte_range(&self, original_range: &Range<usize>) -> Range<usize> {
+        self.convert(original_range, |s| &s.original_bytes, |s| &s.normalized_bytes)
+    }
+
+    fn convert<F, T>(&self, range: &Range<usize>, from: F, to: T) -> Range<usize>
+    where
+        F: Fn(&Segment) -> &Range<usize>,
+        T: Fn(&Segment) -> &Range<usize>,
+    {
+        let end_of_target = self.segments.last().map(|s| to(s).end).unwrap_or(0);
+        // Index of the first segment which is not empty and contains the position
+        let segment_containing = |position: usize| {
+            let index = self.segments.partition_point(|s| from(s).end <= position);
+            self.segments.get(index)
+        };
+        let start = segment_containing(range.start)
+            .map(|s| to(s).start)
+            .unwrap_or(end_of_target);
+        if range.end <= range.start {
+            return start..start;
+        }
+        let end = segment_containing(range.end - 1)
+            .map(|s| to(s).end)
+            .unwrap_or(end_of_target);
+        start..end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions_should_handle_expansions_and_deletions() {
+        // Given
+        let mut alignment = Alignment::default();
+        alignment.push(" ", "");
+        alignment.push("S", "s");
+        alignment.push("ß", "ss");
+        alignment.push("e\u{301}", "e");
+
+        // Then
+        assert_eq!(1..2, alignment.original_char_range(&(0..1)));
+        assert_eq!(2..3, alignment.original_char_range(&(1..2)));
+        assert_eq!(2..3, alignment.original_char_range(&(2..3)));
+        assert_eq!(1..5, alignment.original_char_range(&(0..4)));
+        assert_eq!(1..7, alignment.original_byte_range(&(0..4)));
+        assert_eq!(0..4, alignment.normalized_char_range(&(0..5)));
+        assert_eq!(1..3, alignment.normalized_char_range(&(2..3)));
+        assert_eq!(3..4, alignment.normalized_char_range(&(4..5)));
+        assert_eq!(0..0, alignment.normalized_char_range(&(0..1)));
+        assert_eq!(4..4, alignment.normalized_byte_range(&(7..7)));
+    }
+}
@@ -0,0 +1,209 @@
+use std::ops::Range;
+
+use fnv::FnvHashMap;
+
+use crate::language::Language;
+use crate::string::normalize;
+use crate::token::tokenize;
+
+/// Match of a gazetteer entity value in an input string
+#[derive(Debug, Clone, PartialEq)]
+pub struct GazetteerMatch {
+    /// Entity value, as provided when building the gazetteer
+    pub value: String,
+    /// Range of the matched tokens in the tokenized input
+    pub token_range: Range<usize>,
+    /// Byte range of the match in the input string
+    pub range: Range<usize>,
+    /// Char range of the match in the input string
+    pub char_range: Range<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    children: FnvHashMap<String, usize>,
+    value_index: Option<usize>,
+}
+
+/// Gazetteer storing entity values as normalized token sequences in a trie
+///
+/// Values and inputs are tokenized with `tokenize` and normalized with `normalize`, so that
+/// matching is insensitive to case, diacritics and spacing.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::gazetteer::Gazetteer;
+/// use snips_nlu_utils::language::Language;
+///
+/// let gazetteer = Gazetteer::new(&["the rolling stones", "the stones"], Language::EN);
+/// let matches = gazetteer.find_matches("play thé Rolling   Stones please");
+/// assert_eq!(1, matches.len());
+/// assert_eq!("the rolling stones", matches[0].value);
+/// assert_eq!(1..4, matches[0].token_range);
+/// assert_eq!(5..26, matches[0].range);
+/// assert_eq!(5..25, matches[0].char_range);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Gazetteer {
+    language: Language,
+    nodes: Vec<TrieNode>,
+    values: Vec<String>,
+}
+
+impl Gazetteer {
+    pub fn new<S: AsRef<str>>(values: &[S], language: Language) -> Self {
+        let mut gazetteer = Gazetteer {
+            language,
+            nodes: vec![TrieNode::default()],
+            values: vec![],
+        };
+        for value in values {
+            gazetteer.add_value(value.as_ref());
+        }
+        gazetteer
+    }
+
+    /// Add an entity value to the gazetteer
+    ///
+    /// Values which produce no tokens are ignored. When two values have the same normalized
+    /// tokens, the first one is kept.
+    pub fn add_value(&mut self, value: &str) {
+        let tokens: Vec<String> = tokenize(value, self.language)
+            .into_iter()
+            .map(|t| normalize(&t.value))
+            .collect();
+        if tokens.is_empty() {
+            return;
+        }
+        let mut node_index = 0;
+        for token in tokens {
+            node_index = match self.nodes[node_index].children.get(&token) {
+                Some(child_index) => *child_index,
+                None => {
+                    let child_index = self.nodes.len();
+                    self.nodes.push(TrieNode::default());
+                    self.nodes[node_index].children.insert(token, child_index);
+                    child_index
+                }
+            };
+        }
+        if self.nodes[node_index].value_index.is_none() {
+            self.nodes[node_index].value_index = Some(self.values.len());
+            self.values.push(value.to_string());
+        }
+    }
+
+    /// Number of distinct entity values
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Find the longest non-overlapping matches of entity values in the input
+    ///
+    /// Matches are searched from left to right, and at each position the longest entity value
+    /// is preferred.
+    pub fn find_matches(&self, input: &str) -> Vec<GazetteerMatch> {
+        let tokens = tokenize(input, self.language);
+        let normalized_tokens: Vec<String> = tokens.iter().map(|t| normalize(&t.value)).collect();
+        let mut matches = vec![];
+        let mut start = 0;
+        while start < tokens.len() {
+            match self.longest_match_from(&normalized_tokens[start..]) {
+                Some((length, value_index)) => {
+                    let end = start + length;
+                    let first_token = &tokens[start];
+                    let last_token = &tokens[end - 1];
+                    matches.push(GazetteerMatch {
+                        value: self.values[value_index].clone(),
+                        token_range: start..end,
+                        range: first_token.range.start..last_token.range.end,
+                        char_range: first_token.char_range.start..last_token.char_range.end,
+                    });
+                    start = end;
+                }
+                None => start += 1,
+            }
+        }
+        matches
+    }
+
+    fn longest_match_from(&self, tokens: &[String]) -> Option<(usize, usize)> {
+        let mut node_index = 0;
+        let mut longest_match = None;
+        for (index, token) in tokens.iter().enumerate() {
+            match self.nodes[node_index].children.get(token) {
+                Some(child_index) => node_index = *child_index,
+                None => break,
+            }
+            if let Some(value_index) = self.nodes[node_index].value_index {
+                longest_match = Some((index + 1, value_index));
+            }
+        }
+        longest_match
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_matches_should_return_longest_non_overlapping_matches() {
+        // Given
+        let values = vec!["new york", "new york city", "york city", "paris"];
+        let gazetteer = Gazetteer::new(&values, Language::EN);
+
+        // When
+        let matches = gazetteer.find_matches("from New York City to Paris");
+
+        // Then
+        let expected = vec![
+            GazetteerMatch {
+                value: "new york city".to_string(),
+                token_range: 1..4,
+                range: 5..18,
+                char_range: 5..18,
+            },
+            GazetteerMatch {
+                value: "paris".to_string(),
+                token_range: 5..6,
+                range: 22..27,
+                char_range: 22..27,
+            },
+        ];
+        assert_eq!(expected, matches);
+    }
+
+    #[test]
+    fn find_matches_should_fall_back_to_shorter_prefix() {
+        // Given
+        let gazetteer = Gazetteer::new(&["new york", "new york city"], Language::EN);
+
+        // When
+        let matches = gazetteer.find_matches("new york town");
+
+        // Then
+        assert_eq!(1, matches.len());
+        assert_eq!("new york", matches[0].value);
+        assert_eq!(0..2, matches[0].token_range);
+    }
+
+    #[test]
+    fn find_matches_should_handle_multibyte_chars() {
+        // Given
+        let gazetteer = Gazetteer::new(&["beyonce"], Language::EN);
+
+        // When
+        let matches = gazetteer.find_matches("joue Beyoncé");
+
+        // Then
+        assert_eq!(1, matches.len());
+        assert_eq!(5..13, matches[0].range);
+        assert_eq!(5..12, matches[0].char_range);
+    }
+}
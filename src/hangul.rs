@@ -0,0 +1,260 @@
+const HANGUL_SYLLABLE_START: u32 = 0xAC00;
+const HANGUL_SYLLABLE_END: u32 = 0xD7A3;
+const HANGUL_NB_MEDIALS: u32 = 21;
+const HANGUL_NB_FINALS: u32 = 28;
+const COMPATIBILITY_MEDIAL_START: u32 = 0x314F;
+
+/// Compatibility jamo of the initial consonants, in the order of the syllables block
+const INITIALS: [char; 19] = [
+    'ㄱ', 'ㄲ', 'ㄴ', 'ㄷ', 'ㄸ', 'ㄹ', 'ㅁ', 'ㅂ', 'ㅃ', 'ㅅ', 'ㅆ', 'ㅇ', 'ㅈ', 'ㅉ', 'ㅊ', 'ㅋ',
+    'ㅌ', 'ㅍ', 'ㅎ',
+];
+
+/// Compatibility jamo of the final consonants, in the order of the syllables block
+const FINALS: [char; 27] = [
+    'ㄱ', 'ㄲ', 'ㄳ', 'ㄴ', 'ㄵ', 'ㄶ', 'ㄷ', 'ㄹ', 'ㄺ', 'ㄻ', 'ㄼ', 'ㄽ', 'ㄾ', 'ㄿ', 'ㅀ', 'ㅁ',
+    'ㅂ', 'ㅄ', 'ㅅ', 'ㅆ', 'ㅇ', 'ㅈ', 'ㅊ', 'ㅋ', 'ㅌ', 'ㅍ', 'ㅎ',
+];
+
+/// Whether the char is a precomposed hangul syllable, in the range U+AC00..=U+D7A3
+pub fn is_hangul_syllable(c: char) -> bool {
+    (HANGUL_SYLLABLE_START..=HANGUL_SYLLABLE_END).contains(&(c as u32))
+}
+
+/// Decompose a syllable into the indexes of its initial, medial and optional final consonant
+pub(crate) fn decompose_hangul_syllable(c: char) -> (usize, usize, Option<usize>) {
+    let index = c as u32 - HANGUL_SYLLABLE_START;
+    let initial = index / (HANGUL_NB_MEDIALS * HANGUL_NB_FINALS);
+    let medial = (index % (HANGUL_NB_MEDIALS * HANGUL_NB_FINALS)) / HANGUL_NB_FINALS;
+    let final_consonant = index % HANGUL_NB_FINALS;
+    (
+        initial as usize,
+        medial as usize,
+        if final_consonant == 0 {
+            None
+        } else {
+            Some(final_consonant as usize - 1)
+        },
+    )
+}
+
+/// Decompose a hangul syllable into the compatibility jamo of its initial consonant, medial
+/// vowel and optional final consonant
+///
+/// Return `None` when the char is not a hangul syllable.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::hangul::decompose_syllable;
+///
+/// assert_eq!(Some(('ㅎ', 'ㅏ', Some('ㄴ'))), decompose_syllable('한'));
+/// assert_eq!(Some(('ㄱ', 'ㅏ', None)), decompose_syllable('가'));
+/// assert_eq!(None, decompose_syllable('a'));
+/// ```
+pub fn decompose_syllable(c: char) -> Option<(char, char, Option<char>)> {
+    if !is_hangul_syllable(c) {
+        return None;
+    }
+    let (initial, medial, final_consonant) = decompose_hangul_syllable(c);
+    Some((
+        INITIALS[initial],
+        compatibility_medial(medial),
+        final_consonant.map(|index| FINALS[index]),
+    ))
+}
+
+/// Compose compatibility jamo into a hangul syllable
+///
+/// Return `None` when the jamo cannot be composed, e.g. when the initial is a vowel.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::hangul::compose_syllable;
+///
+/// assert_eq!(Some('한'), compose_syllable('ㅎ', 'ㅏ', Some('ㄴ')));
+/// assert_eq!(Some('닭'), compose_syllable('ㄷ', 'ㅏ', Some('ㄺ')));
+/// assert_eq!(None, compose_syllable('ㅏ', 'ㅏ', None));
+/// ```
+pub fn compose_syllable(
+    initial: char,
+    medial: char,
+    final_consonant: Option<char>,
+) -> Option<char> {
+    let initial = initial_index(initial)?;
+    let medial = medial_index(medial)?;
+    let final_consonant = match final_consonant {
+        Some(c) => final_index(c)? + 1,
+        None => 0,
+    };
+    let index = (initial * HANGUL_NB_MEDIALS as usize + medial) * HANGUL_NB_FINALS as usize
+        + final_consonant;
+    ::std::char::from_u32(HANGUL_SYLLABLE_START + index as u32)
+}
+
+/// Decompose the hangul syllables of the input into compatibility jamo, other chars being kept
+/// unchanged
+///
+/// Compound vowels and compound final consonants, such as "ㅘ" and "ㄺ", are kept as a single
+/// jamo.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::hangul::decompose_jamo;
+///
+/// assert_eq!("ㅎㅏㄴㄱㅜㄱㅇㅓ", decompose_jamo("한국어"));
+/// assert_eq!("ㅇㅘ 1", decompose_jamo("와 1"));
+/// ```
+pub fn decompose_jamo(input: &str) -> String {
+    let mut result = String::with_capacity(input.len() * 2);
+    for c in input.chars() {
+        match decompose_syllable(c) {
+            Some((initial, medial, final_consonant)) => {
+                result.push(initial);
+                result.push(medial);
+                result.extend(final_consonant);
+            }
+            None => result.push(c),
+        }
+    }
+    result
+}
+
+/// Compose sequences of compatibility jamo into hangul syllables, other chars being kept
+/// unchanged
+///
+/// A consonant following a syllable becomes its final consonant, unless it is followed by a
+/// vowel, in which case it starts the next syllable. This reverses `decompose_jamo`.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::hangul::compose_jamo;
+///
+/// assert_eq!("한국어", compose_jamo("ㅎㅏㄴㄱㅜㄱㅇㅓ"));
+/// assert_eq!("닭이 ㅋㅋ", compose_jamo("ㄷㅏㄺㅇㅣ ㅋㅋ"));
+/// ```
+pub fn compose_jamo(input: &str) -> String {
+    let chars: Vec<char> = input.chars().collect();
+    let mut result = String::with_capacity(input.len());
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        let medial = chars.get(index + 1).cloned().filter(|c| is_medial(*c));
+        let syllable = medial.and_then(|medial| {
+            let final_consonant = chars.get(index + 2).cloned().filter(|f| {
+                let next_is_medial = matches!(chars.get(index + 3), Some(c) if is_medial(*c));
+                final_index(*f).is_some() && (!next_is_medial || initial_index(*f).is_none())
+            });
+            compose_syllable(c, medial, final_consonant)
+                .map(|syllable| (syllable, if final_consonant.is_some() { 3 } else { 2 }))
+        });
+        match syllable {
+            Some((syllable, nb_jamo)) => {
+                result.push(syllable);
+                index += nb_jamo;
+            }
+            None => {
+                result.push(c);
+                index += 1;
+            }
+        }
+    }
+    result
+}
+
+/// Return the final consonant of the last syllable of the word, as a compatibility jamo
+///
+/// Return `None` when the last syllable has no final consonant, or when the word does not end
+/// with a hangul syllable.
+pub fn final_consonant(word: &str) -> Option<char> {
+    word.chars()
+        .last()
+        .and_then(decompose_syllable)
+        .and_then(|(_, _, final_consonant)| final_consonant)
+}
+
+/// Whether the last syllable of the word ends with a consonant, which determines the form of
+/// the particles following it, e.g. "을" vs "를" or "이" vs "가"
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::hangul::has_final_consonant;
+///
+/// assert!(has_final_consonant("책"));
+/// assert!(!has_final_consonant("사과"));
+/// ```
+pub fn has_final_consonant(word: &str) -> bool {
+    final_consonant(word).is_some()
+}
+
+/// Whether the last syllable of the word ends with a consonant other than "ㄹ", which determines
+/// the form of particles such as "으로" vs "로"
+pub fn has_final_consonant_except_rieul(word: &str) -> bool {
+    matches!(final_consonant(word), Some(c) if c != 'ㄹ')
+}
+
+fn compatibility_medial(medial: usize) -> char {
+    ::std::char::from_u32(COMPATIBILITY_MEDIAL_START + medial as u32).unwrap()
+}
+
+fn is_medial(c: char) -> bool {
+    medial_index(c).is_some()
+}
+
+fn initial_index(c: char) -> Option<usize> {
+    INITIALS.iter().position(|initial| *initial == c)
+}
+
+fn medial_index(c: char) -> Option<usize> {
+    let code = c as u32;
+    if (COMPATIBILITY_MEDIAL_START..COMPATIBILITY_MEDIAL_START + HANGUL_NB_MEDIALS).contains(&code)
+    {
+        Some((code - COMPATIBILITY_MEDIAL_START) as usize)
+    } else {
+        None
+    }
+}
+
+fn final_index(c: char) -> Option<usize> {
+    FINALS
+        .iter()
+        .position(|final_consonant| *final_consonant == c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compose_jamo_should_reverse_decompose_jamo() {
+        for input in &[
+            "서울특별시",
+            "닭고기와 밥",
+            "값이 얼마예요?",
+            "읽어요",
+            "abc",
+        ] {
+            assert_eq!(*input, compose_jamo(&decompose_jamo(input)));
+        }
+    }
+
+    #[test]
+    fn compose_jamo_should_keep_incomplete_jamo() {
+        assert_eq!("ㅏ간", compose_jamo("ㅏㄱㅏㄴ"));
+        assert_eq!("ㄱㄴ", compose_jamo("ㄱㄴ"));
+    }
+
+    #[test]
+    fn final_consonant_helpers_work() {
+        assert_eq!(Some('ㄹ'), final_consonant("서울"));
+        assert_eq!(None, final_consonant("부"));
+        assert_eq!(None, final_consonant("seoul"));
+        assert!(!has_final_consonant(""));
+        assert!(has_final_consonant_except_rieul("부산"));
+        assert!(!has_final_consonant_except_rieul("서울"));
+    }
+}
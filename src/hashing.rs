@@ -0,0 +1,118 @@
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// 64-bit FNV-1a hash of the string
+///
+/// The hash only depends on the UTF-8 bytes of the string, so it is stable across platforms.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::hashing::fnv1a_64;
+///
+/// assert_eq!(0x8594_4171_f739_67e8, fnv1a_64("foobar"));
+/// ```
+pub fn fnv1a_64(input: &str) -> u64 {
+    fnv1a_64_bytes(FNV_OFFSET_BASIS, input.as_bytes())
+}
+
+/// 64-bit FNV-1a hash of the string, seeded by first hashing the little-endian bytes of the seed
+///
+/// Different seeds give independent hash functions, which is useful to reduce the impact of
+/// collisions by hashing features several times.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::hashing::fnv1a_64_with_seed;
+///
+/// assert_ne!(fnv1a_64_with_seed("foobar", 1), fnv1a_64_with_seed("foobar", 2));
+/// ```
+pub fn fnv1a_64_with_seed(input: &str, seed: u64) -> u64 {
+    let seeded = fnv1a_64_bytes(FNV_OFFSET_BASIS, &seed.to_le_bytes());
+    fnv1a_64_bytes(seeded, input.as_bytes())
+}
+
+fn fnv1a_64_bytes(initial_state: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(initial_state, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// MurmurHash3 x86 32-bit hash of the bytes
+///
+/// Blocks are read as little-endian integers whatever the platform, as done by the reference
+/// implementation on x86.
+pub fn murmurhash3_x86_32(bytes: &[u8], seed: u32) -> u32 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+
+    let mix_block = |k: u32| k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+
+    let mut hash = seed;
+    let mut blocks = bytes.chunks_exact(4);
+    for block in &mut blocks {
+        let k = u32::from_le_bytes([block[0], block[1], block[2], block[3]]);
+        hash ^= mix_block(k);
+        hash = hash.rotate_left(13).wrapping_mul(5).wrapping_add(0xe654_6b64);
+    }
+    let tail = blocks.remainder();
+    if !tail.is_empty() {
+        let k = tail
+            .iter()
+            .rev()
+            .fold(0u32, |k, byte| (k << 8) | u32::from(*byte));
+        hash ^= mix_block(k);
+    }
+
+    hash ^= bytes.len() as u32;
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^ (hash >> 16)
+}
+
+/// Signed MurmurHash3 x86 32-bit hash of the string, which matches
+/// `sklearn.utils.murmurhash3_32(input, seed)` and the hashes used by scikit-learn's
+/// `HashingVectorizer`
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::hashing::murmurhash3_32;
+///
+/// assert_eq!(-156_908_512, murmurhash3_32("foo", 0));
+/// ```
+pub fn murmurhash3_32(input: &str, seed: u32) -> i32 {
+    murmurhash3_x86_32(input.as_bytes(), seed) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a_64_works() {
+        assert_eq!(0xcbf2_9ce4_8422_2325, fnv1a_64(""));
+        assert_eq!(0xaf63_dc4c_8601_ec8c, fnv1a_64("a"));
+        assert_ne!(fnv1a_64("foobar"), fnv1a_64_with_seed("foobar", 0));
+    }
+
+    #[test]
+    fn murmurhash3_x86_32_works() {
+        assert_eq!(0, murmurhash3_x86_32(b"", 0));
+        assert_eq!(0x514e_28b7, murmurhash3_x86_32(b"", 1));
+        assert_eq!(0x248b_fa47, murmurhash3_x86_32(b"hello", 0));
+        assert_eq!(0xfaf6_cdb3, murmurhash3_x86_32(b"Hello, world!", 1234));
+        assert_eq!(
+            0x2e4f_f723,
+            murmurhash3_x86_32(b"The quick brown fox jumps over the lazy dog", 0)
+        );
+    }
+
+    #[test]
+    fn murmurhash3_32_should_match_sklearn() {
+        assert_eq!(-156_908_512, murmurhash3_32("foo", 0));
+    }
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+/// Conversion between the two japanese kana syllabaries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KanaConversion {
+    Preserve,
+    /// Convert katakana to hiragana
+    Hiragana,
+    /// Convert hiragana to katakana
+    Katakana,
+}
+
+impl KanaConversion {
+    pub fn convert(&self, input: &str) -> String {
+        match self {
+            KanaConversion::Preserve => input.to_string(),
+            KanaConversion::Hiragana => katakana_to_hiragana(input),
+            KanaConversion::Katakana => hiragana_to_katakana(input),
+        }
+    }
+}
+
+/// Convert the katakana of the input to hiragana
+///
+/// Katakana which have no hiragana equivalent, such as "ヷ", and the long vowel mark "ー" are
+/// kept unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::japanese::katakana_to_hiragana;
+///
+/// assert_eq!("とうきょう", katakana_to_hiragana("トウキョウ"));
+/// assert_eq!("らーめん", katakana_to_hiragana("ラーメン"));
+/// ```
+pub fn katakana_to_hiragana(input: &str) -> String {
+    input.chars().map(to_hiragana).collect()
+}
+
+/// Convert the hiragana of the input to katakana
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::japanese::hiragana_to_katakana;
+///
+/// assert_eq!("スシ", hiragana_to_katakana("すし"));
+/// ```
+pub fn hiragana_to_katakana(input: &str) -> String {
+    input.chars().map(to_katakana).collect()
+}
+
+/// Convert a katakana char to hiragana, other chars being returned unchanged
+pub fn to_hiragana(c: char) -> char {
+    match c {
+        '\u{30A1}'..='\u{30F6}' | '\u{30FD}'..='\u{30FE}' => {
+            ::std::char::from_u32(c as u32 - 0x60).unwrap_or(c)
+        }
+        _ => c,
+    }
+}
+
+/// Convert a hiragana char to katakana, other chars being returned unchanged
+pub fn to_katakana(c: char) -> char {
+    match c {
+        '\u{3041}'..='\u{3096}' | '\u{309D}'..='\u{309E}' => {
+            ::std::char::from_u32(c as u32 + 0x60).unwrap_or(c)
+        }
+        _ => c,
+    }
+}
+
+/// Whether the char is one of the half-width voiced and semi-voiced sound marks, which combine
+/// with the preceding half-width katakana
+pub fn is_half_width_sound_mark(c: char) -> bool {
+    c == '\u{FF9E}' || c == '\u{FF9F}'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kana_conversions_should_be_reversible() {
+        // Given
+        let hiragana = "ぁあゔゝゞ";
+        let katakana = "ァアヴヽヾ";
+
+        // Then
+        assert_eq!(katakana, hiragana_to_katakana(hiragana));
+        assert_eq!(hiragana, katakana_to_hiragana(katakana));
+        assert_eq!("ヷー漢字", katakana_to_hiragana("ヷー漢字"));
+    }
+}
@@ -1,15 +1,27 @@
 use std::str::FromStr;
 use failure;
 use failure::format_err;
+use serde::{Deserialize, Serialize};
 
-const PUNCTUATION: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+macro_rules! ascii_punctuation {
+    () => {
+        "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~"
+    };
+}
+
+const PUNCTUATION: &str = ascii_punctuation!();
+const DUTCH_PUNCTUATION: &str = concat!(ascii_punctuation!(), "‘’“”„…–—");
+const POLISH_PUNCTUATION: &str = concat!(ascii_punctuation!(), "„”«»…–—");
+const RUSSIAN_PUNCTUATION: &str = concat!(ascii_punctuation!(), "«»„“…–—");
+const SWEDISH_PUNCTUATION: &str = concat!(ascii_punctuation!(), "”’»«…–—");
 const SPACE: &str = " ";
 
 macro_rules! language_enum {
     ([$($language:ident),*]) => {
         /// Enumerates all language supported
         #[allow(non_camel_case_types)]
-        #[derive(Copy,Clone,Debug)]
+        #[derive(Copy,Clone,Debug,PartialEq,Eq,Serialize,Deserialize)]
+        #[serde(rename_all = "lowercase")]
         pub enum Language {
             $( $language, )*
         }
@@ -24,7 +36,7 @@ macro_rules! language_enum {
     }
 }
 
-language_enum!([DE, EN, ES, FR, IT, JA, KO, PT_PT, PT_BR]);
+language_enum!([DE, EN, ES, FR, IT, JA, KO, NL, PL, PT_PT, PT_BR, RU, SV, TR]);
 
 impl FromStr for Language {
     type Err = failure::Error;
@@ -37,8 +49,13 @@ impl FromStr for Language {
             "it" => Ok(Language::IT),
             "ja" => Ok(Language::JA),
             "ko" => Ok(Language::KO),
+            "nl" => Ok(Language::NL),
+            "pl" => Ok(Language::PL),
             "pt_pt" => Ok(Language::PT_PT),
             "pt_br" => Ok(Language::PT_BR),
+            "ru" => Ok(Language::RU),
+            "sv" => Ok(Language::SV),
+            "tr" => Ok(Language::TR),
             _ => Err(format_err!("Unknown language {}", it)),
         }
     }
@@ -54,8 +71,13 @@ impl ToString for Language {
             Language::IT => "it".to_string(),
             Language::JA => "ja".to_string(),
             Language::KO => "ko".to_string(),
+            Language::NL => "nl".to_string(),
+            Language::PL => "pl".to_string(),
             Language::PT_PT => "pt_pt".to_string(),
             Language::PT_BR => "pt_br".to_string(),
+            Language::RU => "ru".to_string(),
+            Language::SV => "sv".to_string(),
+            Language::TR => "tr".to_string(),
         }
     }
 }
@@ -63,6 +85,10 @@ impl ToString for Language {
 impl Language {
     pub fn punctuation(&self) -> &'static str {
         match self {
+            Language::NL => DUTCH_PUNCTUATION,
+            Language::PL => POLISH_PUNCTUATION,
+            Language::RU => RUSSIAN_PUNCTUATION,
+            Language::SV => SWEDISH_PUNCTUATION,
             _ => PUNCTUATION
         }
     }
@@ -73,3 +99,36 @@ impl Language {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_should_be_parsed_and_printed() {
+        for language in Language::all() {
+            assert_eq!(language, Language::from_str(&language.to_string()).unwrap());
+        }
+        assert_eq!(Language::NL, Language::from_str("nl").unwrap());
+        assert_eq!(Language::PL, Language::from_str("PL").unwrap());
+        assert_eq!(Language::RU, Language::from_str("ru").unwrap());
+        assert_eq!(Language::SV, Language::from_str("sv").unwrap());
+        assert_eq!("sv", Language::SV.to_string());
+        assert_eq!(Language::TR, Language::from_str("tr").unwrap());
+        assert_eq!("tr", Language::TR.to_string());
+        assert!(Language::from_str("xx").is_err());
+    }
+
+    #[test]
+    fn punctuation_should_depend_on_language() {
+        assert!(Language::RU.punctuation().contains('«'));
+        assert!(Language::PL.punctuation().contains('„'));
+        assert!(Language::NL.punctuation().contains('’'));
+        assert!(Language::SV.punctuation().contains('”'));
+        assert!(!Language::EN.punctuation().contains('«'));
+        assert_eq!(Language::EN.punctuation(), Language::TR.punctuation());
+        for language in &[Language::NL, Language::PL, Language::RU, Language::SV] {
+            assert!(language.punctuation().starts_with(Language::EN.punctuation()));
+        }
+    }
+}
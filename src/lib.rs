@@ -1,4 +1,17 @@
+pub mod alignment;
+pub mod gazetteer;
+pub mod hangul;
+pub mod hashing;
+pub mod japanese;
 pub mod language;
+pub mod multi_pattern;
+pub mod normalization;
+pub mod offset;
+pub mod phonetic;
 pub mod range;
 pub mod string;
+pub mod tagging;
 pub mod token;
+pub mod transliteration;
+pub mod vectorizer;
+pub mod word_clusters;
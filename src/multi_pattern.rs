@@ -0,0 +1,142 @@
+use std::ops::Range;
+
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+
+use crate::offset::OffsetIndex;
+use crate::string::{normalize, normalize_with_alignment};
+
+/// Match of a pattern in an input string
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternMatch {
+    /// Index of the matched pattern, in the order the patterns were provided
+    pub pattern_index: usize,
+    /// Byte range of the match in the original input
+    pub range: Range<usize>,
+    /// Char range of the match in the original input
+    pub char_range: Range<usize>,
+}
+
+/// Multi-pattern matcher, based on Aho-Corasick, which is insensitive to case and diacritics
+///
+/// Both patterns and inputs are normalized, but matches are reported as ranges in the original,
+/// un-normalized input.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::multi_pattern::MultiPatternMatcher;
+///
+/// let matcher = MultiPatternMatcher::new(&["beyonce", "Céline Dion"]);
+/// let matches = matcher.find_matches("play CÉLINE DION then Beyoncé");
+/// assert_eq!(2, matches.len());
+/// assert_eq!(1, matches[0].pattern_index);
+/// assert_eq!(5..17, matches[0].range);
+/// assert_eq!(0, matches[1].pattern_index);
+/// assert_eq!(23..31, matches[1].range);
+/// assert_eq!(22..29, matches[1].char_range);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiPatternMatcher {
+    automaton: AhoCorasick,
+    pattern_indexes: Vec<usize>,
+}
+
+impl MultiPatternMatcher {
+    /// Build a matcher from the patterns
+    ///
+    /// Patterns which are empty after normalization are ignored.
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Self {
+        let (pattern_indexes, normalized_patterns): (Vec<usize>, Vec<String>) = patterns
+            .iter()
+            .map(|p| normalize(p.as_ref()))
+            .enumerate()
+            .filter(|(_, p)| !p.is_empty())
+            .unzip();
+        let automaton = AhoCorasickBuilder::new()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(normalized_patterns);
+        MultiPatternMatcher {
+            automaton,
+            pattern_indexes,
+        }
+    }
+
+    /// Find the non-overlapping matches of the patterns in the input
+    ///
+    /// Matches are searched from left to right, and at each position the longest pattern is
+    /// preferred.
+    pub fn find_matches(&self, input: &str) -> Vec<PatternMatch> {
+        let (normalized_input, alignment) = normalize_with_alignment(input);
+        let offsets = OffsetIndex::new(input);
+        self.automaton
+            .find_iter(&normalized_input)
+            .map(|m| {
+                let range = alignment.original_byte_range(&(m.start()..m.end()));
+                PatternMatch {
+                    pattern_index: self.pattern_indexes[m.pattern()],
+                    char_range: offsets.byte_range_to_char_range(&range),
+                    range,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_matches_should_prefer_longest_patterns() {
+        // Given
+        let matcher = MultiPatternMatcher::new(&["new york", "york", "new york city"]);
+
+        // When
+        let matches = matcher.find_matches("from New York City to york");
+
+        // Then
+        let expected = vec![
+            PatternMatch {
+                pattern_index: 2,
+                range: 5..18,
+                char_range: 5..18,
+            },
+            PatternMatch {
+                pattern_index: 1,
+                range: 22..26,
+                char_range: 22..26,
+            },
+        ];
+        assert_eq!(expected, matches);
+    }
+
+    #[test]
+    fn find_matches_should_map_offsets_after_length_changes() {
+        // Given
+        let matcher = MultiPatternMatcher::new(&["cafe"]);
+        // "e" followed by a combining acute accent, which is removed by normalization
+        let input = "Un Cafe\u{301} et un İcafé";
+
+        // When
+        let matches = matcher.find_matches(input);
+
+        // Then
+        assert_eq!(2, matches.len());
+        assert_eq!("Cafe\u{301}", &input[matches[0].range.clone()]);
+        assert_eq!(3..8, matches[0].char_range);
+        assert_eq!("café", &input[matches[1].range.clone()]);
+    }
+
+    #[test]
+    fn find_matches_should_ignore_empty_patterns() {
+        // Given
+        let matcher = MultiPatternMatcher::new(&["  ", "foo"]);
+
+        // When
+        let matches = matcher.find_matches("a foo");
+
+        // Then
+        assert_eq!(1, matches.len());
+        assert_eq!(1, matches[0].pattern_index);
+    }
+}
@@ -0,0 +1,456 @@
+use caseless::Caseless;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::alignment::Alignment;
+use crate::japanese::KanaConversion;
+use crate::language::Language;
+use crate::string::{ascii_fold, remove_diacritics, split_combining_sequences, turkic_lowercase_i};
+
+/// Unicode normalization form applied before the other normalization steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnicodeForm {
+    None,
+    /// Canonical composition, e.g. "e" followed by a combining acute accent becomes "é"
+    Nfc,
+    /// Compatibility composition, which also folds variants such as full-width letters and
+    /// ligatures
+    Nfkc,
+}
+
+/// Case transformation applied by a `Normalizer`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Casing {
+    Preserve,
+    Lowercase,
+    /// Unicode full case folding, which unlike lowercasing maps "ß" to "ss"
+    Fold,
+}
+
+/// Options of a `Normalizer`
+///
+/// The default options correspond to `string::normalize`: trim, remove diacritics and
+/// lowercase. Options can be serialized, so that they can be stored along with a trained model
+/// and the same normalization is applied at inference.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NormalizationOptions {
+    pub unicode_form: UnicodeForm,
+    pub kana_conversion: KanaConversion,
+    pub remove_diacritics: bool,
+    /// Characters which keep their diacritics when `remove_diacritics` is enabled, such as "ñ"
+    /// in spanish. They are compared after the unicode form is applied, and in lowercase.
+    pub preserved_characters: Vec<char>,
+    /// Fold letters and punctuation to their ASCII equivalents, see `string::ascii_fold`
+    pub ascii_folding: bool,
+    /// Remove unicode punctuation characters
+    pub strip_punctuation: bool,
+    pub casing: Casing,
+    /// Language whose rules are used for the case transformation, such as the turkish dotted
+    /// and dotless i
+    pub language: Option<Language>,
+    /// Replace sequences of whitespaces with a single space
+    pub collapse_whitespaces: bool,
+    pub trim: bool,
+}
+
+impl Default for NormalizationOptions {
+    fn default() -> Self {
+        NormalizationOptions {
+            unicode_form: UnicodeForm::None,
+            kana_conversion: KanaConversion::Preserve,
+            remove_diacritics: true,
+            preserved_characters: vec![],
+            ascii_folding: false,
+            strip_punctuation: false,
+            casing: Casing::Lowercase,
+            language: None,
+            collapse_whitespaces: false,
+            trim: true,
+        }
+    }
+}
+
+impl NormalizationOptions {
+    /// Options suited to japanese, which unify the full-width and half-width forms of latin
+    /// letters, digits and katakana, and optionally convert kana to a single syllabary
+    ///
+    /// Diacritics are kept, as they carry the voiced sound marks of kana.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use snips_nlu_utils::japanese::KanaConversion;
+    /// use snips_nlu_utils::normalization::{NormalizationOptions, Normalizer};
+    ///
+    /// let options = NormalizationOptions::japanese(KanaConversion::Katakana);
+    /// let normalizer = Normalizer::new(options);
+    /// assert_eq!("ガッコウ abc 123", normalizer.normalize("ｶﾞｯｺｳ ＡＢＣ １２３"));
+    /// assert_eq!("ガッコウ", normalizer.normalize("がっこう"));
+    /// ```
+    pub fn japanese(kana_conversion: KanaConversion) -> Self {
+        NormalizationOptions {
+            unicode_form: UnicodeForm::Nfkc,
+            kana_conversion,
+            remove_diacritics: false,
+            language: Some(Language::JA),
+            ..Default::default()
+        }
+    }
+}
+
+/// Configurable normalization pipeline
+///
+/// The normalization steps are applied in the following order:
+/// 1) unicode normalization form and kana conversion
+/// 2) diacritics removal and ASCII folding
+/// 3) punctuation removal
+/// 4) case transformation
+/// 5) whitespaces collapsing
+/// 6) trimming
+///
+/// Each step is applied on sequences made of a char and its combining marks, which makes it
+/// possible to keep track of the alignment with the original string.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::normalization::{NormalizationOptions, Normalizer};
+///
+/// let normalizer = Normalizer::new(NormalizationOptions {
+///     preserved_characters: vec!['ñ'],
+///     strip_punctuation: true,
+///     collapse_whitespaces: true,
+///     ..Default::default()
+/// });
+/// assert_eq!("el niño esta aqui", normalizer.normalize(" ¡El   Niño está aquí! "));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Normalizer {
+    options: NormalizationOptions,
+}
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        Self::new(NormalizationOptions::default())
+    }
+}
+
+impl Normalizer {
+    pub fn new(options: NormalizationOptions) -> Self {
+        Normalizer { options }
+    }
+
+    pub fn options(&self) -> &NormalizationOptions {
+        &self.options
+    }
+
+    pub fn normalize(&self, input: &str) -> String {
+        self.normalize_with_alignment(input).0
+    }
+
+    /// Normalize the input and return the alignment between the input and the normalized
+    /// string
+    pub fn normalize_with_alignment(&self, input: &str) -> (String, Alignment) {
+        let sequences: Vec<&str> = split_combining_sequences(input).collect();
+        let mut outputs: Vec<String> = sequences
+            .iter()
+            .map(|sequence| self.normalize_sequence(sequence))
+            .collect();
+        if self.options.casing == Casing::Lowercase {
+            lowercase_outputs(&mut outputs);
+        }
+        if self.options.collapse_whitespaces {
+            collapse_whitespaces(&mut outputs);
+        }
+        if self.options.trim {
+            trim_outputs(&mut outputs);
+        }
+        let mut normalized = String::with_capacity(input.len());
+        let mut alignment = Alignment::default();
+        for (sequence, output) in sequences.into_iter().zip(outputs) {
+            alignment.push(sequence, &output);
+            normalized.push_str(&output);
+        }
+        (normalized, alignment)
+    }
+
+    fn normalize_sequence(&self, sequence: &str) -> String {
+        let mut output: String = match self.options.unicode_form {
+            UnicodeForm::None => sequence.to_string(),
+            UnicodeForm::Nfc => sequence.nfc().collect(),
+            UnicodeForm::Nfkc => sequence.nfkc().collect(),
+        };
+        if self.options.kana_conversion != KanaConversion::Preserve {
+            output = self.options.kana_conversion.convert(&output);
+        }
+        // The turkish capital i must be lowercased before its dot is removed with the diacritics
+        if self.options.casing != Casing::Preserve && self.options.language == Some(Language::TR) {
+            output = turkic_lowercase_i(&output);
+        }
+        if self.options.remove_diacritics {
+            output = output
+                .chars()
+                .flat_map(|c| {
+                    if self.is_preserved(c) {
+                        Some(c)
+                    } else if is_combining_mark(c) {
+                        None
+                    } else {
+                        remove_diacritics(c.encode_utf8(&mut [0; 4])).chars().next()
+                    }
+                })
+                .collect();
+        }
+        if self.options.ascii_folding {
+            output = output
+                .chars()
+                .map(|c| {
+                    if self.is_preserved(c) {
+                        c.to_string()
+                    } else {
+                        ascii_fold(c.encode_utf8(&mut [0; 4]))
+                    }
+                })
+                .collect();
+        }
+        if self.options.strip_punctuation {
+            output = output.chars().filter(|c| !is_punctuation(*c)).collect();
+        }
+        if self.options.casing == Casing::Fold {
+            output = output.chars().default_case_fold().collect();
+        }
+        output
+    }
+
+    fn is_preserved(&self, c: char) -> bool {
+        let preserved = &self.options.preserved_characters;
+        !preserved.is_empty()
+            && c.to_lowercase()
+                .any(|lowercase| preserved.contains(&lowercase))
+    }
+}
+
+fn is_punctuation(c: char) -> bool {
+    lazy_static! {
+        static ref PUNCTUATION_REGEX: Regex = Regex::new(r"^\p{P}$").unwrap();
+    }
+    PUNCTUATION_REGEX.is_match(c.encode_utf8(&mut [0; 4]))
+}
+
+/// Lowercase the outputs, taking into account the context of the whole string, which is needed
+/// for the greek final sigma
+fn lowercase_outputs(outputs: &mut [String]) {
+    let lowercased = outputs.concat().to_lowercase();
+    let mut index = 0;
+    for output in outputs.iter_mut() {
+        let length: usize = output
+            .chars()
+            .flat_map(char::to_lowercase)
+            .map(char::len_utf8)
+            .sum();
+        *output = lowercased[index..index + length].to_string();
+        index += length;
+    }
+}
+
+fn collapse_whitespaces(outputs: &mut [String]) {
+    let mut previous_is_whitespace = false;
+    for output in outputs.iter_mut() {
+        let mut collapsed = String::with_capacity(output.len());
+        for c in output.chars() {
+            if c.is_whitespace() {
+                if !previous_is_whitespace {
+                    collapsed.push(' ');
+                }
+                previous_is_whitespace = true;
+            } else {
+                collapsed.push(c);
+                previous_is_whitespace = false;
+            }
+        }
+        *output = collapsed;
+    }
+}
+
+fn trim_outputs(outputs: &mut [String]) {
+    for output in outputs.iter_mut() {
+        *output = output.trim_start().to_string();
+        if !output.is_empty() {
+            break;
+        }
+    }
+    for output in outputs.iter_mut().rev() {
+        *output = output.trim_end().to_string();
+        if !output.is_empty() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::string::normalize;
+
+    #[test]
+    fn default_normalizer_should_match_normalize() {
+        let normalizer = Normalizer::default();
+        for input in &["  HelöÀ ", "", "  ", "Cafe\u{301}  ΟΔΟΣ İ", "ﬁ ｗｉｄｅ"] {
+            assert_eq!(normalize(input), normalizer.normalize(input));
+        }
+    }
+
+    #[test]
+    fn normalizer_should_apply_unicode_forms() {
+        // Given
+        let nfc = Normalizer::new(NormalizationOptions {
+            unicode_form: UnicodeForm::Nfc,
+            remove_diacritics: false,
+            ..Default::default()
+        });
+        let nfkc = Normalizer::new(NormalizationOptions {
+            unicode_form: UnicodeForm::Nfkc,
+            ..Default::default()
+        });
+
+        // Then
+        assert_eq!("café", nfc.normalize("Cafe\u{301}"));
+        assert_eq!("fi wide 2", nfkc.normalize("ﬁ ｗｉｄｅ ²"));
+    }
+
+    #[test]
+    fn normalizer_should_preserve_characters() {
+        // Given
+        let normalizer = Normalizer::new(NormalizationOptions {
+            unicode_form: UnicodeForm::Nfc,
+            preserved_characters: vec!['ñ'],
+            ..Default::default()
+        });
+
+        // Then
+        assert_eq!("españa señor", normalizer.normalize("ESPAÑA Sen\u{303}or"));
+        assert_eq!("ñandu", normalizer.normalize("N\u{303}andú"));
+    }
+
+    #[test]
+    fn normalizer_should_apply_case_folding() {
+        // Given
+        let normalizer = Normalizer::new(NormalizationOptions {
+            casing: Casing::Fold,
+            ..Default::default()
+        });
+
+        // When
+        let (normalized, alignment) = normalizer.normalize_with_alignment("Straße");
+
+        // Then
+        assert_eq!("strasse", normalized);
+        assert_eq!(normalizer.normalize("STRASSE"), normalized);
+        assert_eq!(4..5, alignment.original_char_range(&(4..6)));
+    }
+
+    #[test]
+    fn normalizer_should_lowercase_according_to_language() {
+        // Given
+        let turkish_lowercase = Normalizer::new(NormalizationOptions {
+            language: Some(Language::TR),
+            ..Default::default()
+        });
+        let turkish_fold = Normalizer::new(NormalizationOptions {
+            casing: Casing::Fold,
+            remove_diacritics: false,
+            language: Some(Language::TR),
+            ..Default::default()
+        });
+
+        // Then
+        assert_eq!(
+            "ıspanak istanbul",
+            turkish_lowercase.normalize("ISPANAK İSTANBUL")
+        );
+        assert_eq!("ıi", turkish_fold.normalize("Iİ"));
+        assert_eq!("σοφος", turkish_lowercase.normalize("ΣΟΦΟΣ"));
+    }
+
+    #[test]
+    fn normalizer_should_apply_ascii_folding() {
+        // Given
+        let normalizer = Normalizer::new(NormalizationOptions {
+            ascii_folding: true,
+            ..Default::default()
+        });
+        let input = "Røyksopp Œuvre";
+
+        // When
+        let (normalized, alignment) = normalizer.normalize_with_alignment(input);
+
+        // Then
+        assert_eq!("royksopp oeuvre", normalized);
+        assert_eq!("Œ", &input[alignment.original_byte_range(&(9..10))]);
+    }
+
+    #[test]
+    fn japanese_normalizer_should_keep_alignment() {
+        // Given
+        let normalizer = Normalizer::new(NormalizationOptions::japanese(KanaConversion::Hiragana));
+        let input = "ｶﾞｯｺｳ ｘ２";
+
+        // When
+        let (normalized, alignment) = normalizer.normalize_with_alignment(input);
+
+        // Then
+        assert_eq!("がっこう x2", normalized);
+        assert_eq!("ｶﾞ", &input[alignment.original_byte_range(&(0..3))]);
+        assert_eq!(0..1, alignment.normalized_char_range(&(0..2)));
+        assert_eq!("ｘ２", &input[alignment.original_byte_range(&(13..15))]);
+    }
+
+    #[test]
+    fn normalizer_should_keep_alignment() {
+        // Given
+        let normalizer = Normalizer::new(NormalizationOptions {
+            strip_punctuation: true,
+            collapse_whitespaces: true,
+            ..Default::default()
+        });
+        let input = "  Hello , \t Wörld!  ";
+
+        // When
+        let (normalized, alignment) = normalizer.normalize_with_alignment(input);
+
+        // Then
+        assert_eq!("hello world", normalized);
+        assert_eq!("Hello", &input[alignment.original_byte_range(&(0..5))]);
+        assert_eq!("Wörld", &input[alignment.original_byte_range(&(6..11))]);
+        assert_eq!(6..11, alignment.normalized_byte_range(&(12..18)));
+    }
+
+    #[test]
+    fn options_should_be_serializable() {
+        // Given
+        let options = NormalizationOptions {
+            unicode_form: UnicodeForm::Nfkc,
+            preserved_characters: vec!['ñ'],
+            casing: Casing::Preserve,
+            language: Some(Language::PT_BR),
+            ..Default::default()
+        };
+
+        // When
+        let serialized = serde_json::to_string(&options).unwrap();
+        let deserialized: NormalizationOptions = serde_json::from_str(&serialized).unwrap();
+        let partial: NormalizationOptions =
+            serde_json::from_str(r#"{"unicode_form": "nfc"}"#).unwrap();
+
+        // Then
+        assert_eq!(options, deserialized);
+        assert_eq!(UnicodeForm::Nfc, partial.unicode_form);
+        assert_eq!(Casing::Lowercase, partial.casing);
+    }
+}
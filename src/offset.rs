@@ -0,0 +1,363 @@
+use std::cmp::min;
+use std::fmt;
+use std::ops::Range;
+
+use failure::Fail;
+
+/// Error returned by the strict index conversions of `OffsetIndex`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OffsetError {
+    OutOfBounds { index: usize, length: usize },
+    NotOnCharBoundary { index: usize },
+    InvertedRange { start: usize, end: usize },
+}
+
+impl fmt::Display for OffsetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OffsetError::OutOfBounds { index, length } => write!(
+                f,
+                "Index {} is past the end of the string, of length {}",
+                index, length
+            ),
+            OffsetError::NotOnCharBoundary { index } => {
+                write!(f, "Index {} is not on a char boundary", index)
+            }
+            OffsetError::InvertedRange { start, end } => {
+                write!(f, "Range {}..{} ends before it starts", start, end)
+            }
+        }
+    }
+}
+
+impl Fail for OffsetError {}
+
+/// Index of the char boundaries of a string, built once in O(n), which converts between byte,
+/// char and UTF-16 indexes in O(log n), or in O(1) for ASCII strings
+///
+/// Indexes which fall inside a char are rounded up to the next char boundary, and indexes past
+/// the end of the string are clamped to its length, as done by `string::convert_to_char_index`.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::offset::OffsetIndex;
+///
+/// let index = OffsetIndex::new("Hellö 😀 !");
+/// assert_eq!(5, index.byte_to_char_index(5));
+/// assert_eq!(7, index.char_to_byte_index(6));
+/// assert_eq!(8, index.char_to_utf16_index(7));
+/// assert_eq!(6..8, index.byte_range_to_char_range(&(7..12)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OffsetIndex {
+    nb_bytes: usize,
+    /// Byte offset of each char, followed by the length of the string, left empty for ASCII
+    byte_offsets: Vec<usize>,
+    /// Char index of each char encoded as a UTF-16 surrogate pair, usually empty
+    astral_chars: Vec<usize>,
+}
+
+impl OffsetIndex {
+    pub fn new(string: &str) -> Self {
+        if string.is_ascii() {
+            return Self {
+                nb_bytes: string.len(),
+                byte_offsets: vec![],
+                astral_chars: vec![],
+            };
+        }
+        let mut byte_offsets = Vec::with_capacity(string.chars().count() + 1);
+        let mut astral_chars = vec![];
+        for (byte_offset, c) in string.char_indices() {
+            if c.len_utf16() > 1 {
+                astral_chars.push(byte_offsets.len());
+            }
+            byte_offsets.push(byte_offset);
+        }
+        byte_offsets.push(string.len());
+        Self {
+            nb_bytes: string.len(),
+            byte_offsets,
+            astral_chars,
+        }
+    }
+
+    fn is_ascii(&self) -> bool {
+        self.byte_offsets.is_empty()
+    }
+
+    pub fn nb_bytes(&self) -> usize {
+        self.nb_bytes
+    }
+
+    pub fn nb_chars(&self) -> usize {
+        if self.is_ascii() {
+            self.nb_bytes
+        } else {
+            self.byte_offsets.len() - 1
+        }
+    }
+
+    pub fn nb_utf16_units(&self) -> usize {
+        self.nb_chars() + self.astral_chars.len()
+    }
+
+    pub fn byte_to_char_index(&self, byte_index: usize) -> usize {
+        if self.is_ascii() {
+            return min(byte_index, self.nb_bytes);
+        }
+        boundary_index(&self.byte_offsets, byte_index)
+    }
+
+    pub fn char_to_byte_index(&self, char_index: usize) -> usize {
+        if self.is_ascii() {
+            return min(char_index, self.nb_bytes);
+        }
+        self.byte_offsets[min(char_index, self.nb_chars())]
+    }
+
+    pub fn utf16_to_char_index(&self, utf16_index: usize) -> usize {
+        let utf16_index = min(utf16_index, self.nb_utf16_units());
+        utf16_index - self.nb_astral_chars_ending_before(utf16_index)
+    }
+
+    pub fn char_to_utf16_index(&self, char_index: usize) -> usize {
+        let char_index = min(char_index, self.nb_chars());
+        char_index
+            + self
+                .astral_chars
+                .partition_point(|astral_char| *astral_char < char_index)
+    }
+
+    pub fn byte_to_utf16_index(&self, byte_index: usize) -> usize {
+        self.char_to_utf16_index(self.byte_to_char_index(byte_index))
+    }
+
+    pub fn utf16_to_byte_index(&self, utf16_index: usize) -> usize {
+        self.char_to_byte_index(self.utf16_to_char_index(utf16_index))
+    }
+
+    /// Strict version of `byte_to_char_index`, which fails instead of rounding or clamping
+    pub fn try_byte_to_char_index(&self, byte_index: usize) -> Result<usize, OffsetError> {
+        if self.is_ascii() {
+            return check_bounds(byte_index, self.nb_bytes);
+        }
+        exact_boundary_index(&self.byte_offsets, byte_index)
+    }
+
+    /// Strict version of `char_to_byte_index`, which fails instead of clamping
+    pub fn try_char_to_byte_index(&self, char_index: usize) -> Result<usize, OffsetError> {
+        check_bounds(char_index, self.nb_chars())?;
+        Ok(self.char_to_byte_index(char_index))
+    }
+
+    /// Strict version of `utf16_to_char_index`, which fails instead of rounding or clamping
+    pub fn try_utf16_to_char_index(&self, utf16_index: usize) -> Result<usize, OffsetError> {
+        check_bounds(utf16_index, self.nb_utf16_units())?;
+        let nb_astral_chars = self.nb_astral_chars_ending_before(utf16_index);
+        let is_inside_astral_char = matches!(
+            self.astral_chars.get(nb_astral_chars),
+            Some(astral_char) if astral_char + nb_astral_chars + 1 == utf16_index
+        );
+        if is_inside_astral_char {
+            return Err(OffsetError::NotOnCharBoundary { index: utf16_index });
+        }
+        Ok(utf16_index - nb_astral_chars)
+    }
+
+    /// Strict version of `char_to_utf16_index`, which fails instead of clamping
+    pub fn try_char_to_utf16_index(&self, char_index: usize) -> Result<usize, OffsetError> {
+        check_bounds(char_index, self.nb_chars())?;
+        Ok(self.char_to_utf16_index(char_index))
+    }
+
+    pub fn byte_range_to_char_range(&self, range: &Range<usize>) -> Range<usize> {
+        self.byte_to_char_index(range.start)..self.byte_to_char_index(range.end)
+    }
+
+    pub fn char_range_to_byte_range(&self, range: &Range<usize>) -> Range<usize> {
+        self.char_to_byte_index(range.start)..self.char_to_byte_index(range.end)
+    }
+
+    pub fn utf16_range_to_char_range(&self, range: &Range<usize>) -> Range<usize> {
+        self.utf16_to_char_index(range.start)..self.utf16_to_char_index(range.end)
+    }
+
+    pub fn char_range_to_utf16_range(&self, range: &Range<usize>) -> Range<usize> {
+        self.char_to_utf16_index(range.start)..self.char_to_utf16_index(range.end)
+    }
+
+    pub fn try_byte_range_to_char_range(
+        &self,
+        range: &Range<usize>,
+    ) -> Result<Range<usize>, OffsetError> {
+        check_range(range)?;
+        Ok(self.try_byte_to_char_index(range.start)?..self.try_byte_to_char_index(range.end)?)
+    }
+
+    pub fn try_char_range_to_byte_range(
+        &self,
+        range: &Range<usize>,
+    ) -> Result<Range<usize>, OffsetError> {
+        check_range(range)?;
+        Ok(self.try_char_to_byte_index(range.start)?..self.try_char_to_byte_index(range.end)?)
+    }
+
+    pub fn try_utf16_range_to_char_range(
+        &self,
+        range: &Range<usize>,
+    ) -> Result<Range<usize>, OffsetError> {
+        check_range(range)?;
+        Ok(self.try_utf16_to_char_index(range.start)?..self.try_utf16_to_char_index(range.end)?)
+    }
+
+    pub fn try_char_range_to_utf16_range(
+        &self,
+        range: &Range<usize>,
+    ) -> Result<Range<usize>, OffsetError> {
+        check_range(range)?;
+        Ok(self.try_char_to_utf16_index(range.start)?..self.try_char_to_utf16_index(range.end)?)
+    }
+
+    /// Number of astral chars whose two UTF-16 units both lie before the UTF-16 index
+    ///
+    /// The k-th astral char starts at the UTF-16 index `astral_chars[k] + k`, which is strictly
+    /// increasing with k, hence the binary search over k.
+    fn nb_astral_chars_ending_before(&self, utf16_index: usize) -> usize {
+        let (mut low, mut high) = (0, self.astral_chars.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            if self.astral_chars[mid] + mid + 1 < utf16_index {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+}
+
+fn check_bounds(index: usize, length: usize) -> Result<usize, OffsetError> {
+    if index > length {
+        return Err(OffsetError::OutOfBounds { index, length });
+    }
+    Ok(index)
+}
+
+pub(crate) fn check_range(range: &Range<usize>) -> Result<(), OffsetError> {
+    if range.start > range.end {
+        return Err(OffsetError::InvertedRange {
+            start: range.start,
+            end: range.end,
+        });
+    }
+    Ok(())
+}
+
+/// Index of the char boundary at the offset, the offsets being sorted and ending with the total
+/// length
+fn exact_boundary_index(offsets: &[usize], offset: usize) -> Result<usize, OffsetError> {
+    let length = offsets[offsets.len() - 1];
+    check_bounds(offset, length)?;
+    offsets
+        .binary_search(&offset)
+        .map_err(|_| OffsetError::NotOnCharBoundary { index: offset })
+}
+
+/// Index of the first char boundary at or after the offset, the offsets being sorted and ending
+/// with the total length
+fn boundary_index(offsets: &[usize], offset: usize) -> usize {
+    min(
+        offsets.partition_point(|boundary| *boundary < offset),
+        offsets.len() - 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_index_should_match_char_iteration() {
+        for string in &["", "abc", "Hellö !!", "日本語 😀 x", "😀😀"] {
+            // Given
+            let index = OffsetIndex::new(string);
+            let mut byte_offsets: Vec<usize> = string.char_indices().map(|(i, _)| i).collect();
+            byte_offsets.push(string.len());
+
+            // Then
+            assert_eq!(string.chars().count(), index.nb_chars());
+            assert_eq!(string.encode_utf16().count(), index.nb_utf16_units());
+            for (char_index, byte_offset) in byte_offsets.iter().enumerate() {
+                let utf16_offset: usize =
+                    string.chars().take(char_index).map(char::len_utf16).sum();
+                assert_eq!(char_index, index.byte_to_char_index(*byte_offset));
+                assert_eq!(*byte_offset, index.char_to_byte_index(char_index));
+                assert_eq!(utf16_offset, index.char_to_utf16_index(char_index));
+                assert_eq!(char_index, index.utf16_to_char_index(utf16_offset));
+                assert_eq!(*byte_offset, index.utf16_to_byte_index(utf16_offset));
+            }
+        }
+    }
+
+    #[test]
+    fn offset_index_should_round_up_and_clamp_indexes() {
+        // Given
+        let index = OffsetIndex::new("aé😀b");
+
+        // Then
+        assert_eq!(2, index.byte_to_char_index(2));
+        assert_eq!(3, index.byte_to_char_index(4));
+        assert_eq!(3, index.utf16_to_char_index(4));
+        assert_eq!(4, index.byte_to_char_index(100));
+        assert_eq!(8, index.char_to_byte_index(100));
+        assert_eq!(5, index.char_to_utf16_index(100));
+        assert_eq!(0, OffsetIndex::new("").byte_to_char_index(3));
+        assert_eq!(2, OffsetIndex::new("😀😀").utf16_to_char_index(3));
+    }
+
+    #[test]
+    fn try_conversions_should_reject_invalid_indexes() {
+        // Given
+        let index = OffsetIndex::new("aé😀b");
+
+        // Then
+        assert_eq!(Ok(3), index.try_byte_to_char_index(7));
+        assert_eq!(Ok(8), index.try_char_to_byte_index(4));
+        assert_eq!(Ok(2..3), index.try_utf16_range_to_char_range(&(2..4)));
+        assert_eq!(
+            Err(OffsetError::NotOnCharBoundary { index: 2 }),
+            index.try_byte_to_char_index(2)
+        );
+        assert_eq!(
+            Err(OffsetError::NotOnCharBoundary { index: 3 }),
+            index.try_utf16_to_char_index(3)
+        );
+        assert_eq!(
+            Err(OffsetError::OutOfBounds {
+                index: 9,
+                length: 8
+            }),
+            index.try_byte_to_char_index(9)
+        );
+        assert_eq!(
+            Err(OffsetError::OutOfBounds {
+                index: 5,
+                length: 4
+            }),
+            index.try_char_range_to_byte_range(&(0..5))
+        );
+        assert_eq!(
+            Err(OffsetError::InvertedRange { start: 3, end: 1 }),
+            index.try_byte_range_to_char_range(&Range { start: 3, end: 1 })
+        );
+        assert_eq!(
+            Err(OffsetError::OutOfBounds {
+                index: 4,
+                length: 3
+            }),
+            OffsetIndex::new("abc").try_char_to_utf16_index(4)
+        );
+    }
+}
@@ -0,0 +1,1034 @@
+use crate::language::Language;
+use crate::string::remove_diacritics;
+
+const DOUBLE_METAPHONE_MAX_LENGTH: usize = 4;
+
+/// Primary and alternate Double Metaphone codes of a string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoubleMetaphone {
+    pub primary: String,
+    pub alternate: String,
+}
+
+/// Compute the phonetic key of a string using the encoding which fits the language
+///
+/// - EN: primary Double Metaphone code
+/// - DE: Kölner Phonetik
+/// - FR: `french_phonetic_key`
+/// - ES: `spanish_phonetic_key`
+///
+/// `None` is returned for other languages.
+pub fn phonetic_key(input: &str, language: Language) -> Option<String> {
+    match language {
+        Language::EN => Some(double_metaphone(input).primary),
+        Language::DE => Some(cologne_phonetics(input)),
+        Language::FR => Some(french_phonetic_key(input)),
+        Language::ES => Some(spanish_phonetic_key(input)),
+        _ => None,
+    }
+}
+
+/// Compute the Double Metaphone codes of an english string, after removing diacritics
+///
+/// This follows the original algorithm by Lawrence Philips, with codes of at most 4 characters.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::phonetic::double_metaphone;
+///
+/// let codes = double_metaphone("Schmidt");
+/// assert_eq!("XMT", codes.primary);
+/// assert_eq!("SMT", codes.alternate);
+/// assert_eq!(double_metaphone("Smith").primary, double_metaphone("Smyth").primary);
+/// ```
+pub fn double_metaphone(input: &str) -> DoubleMetaphone {
+    let value: Vec<char> = remove_diacritics(input.trim()).to_uppercase().chars().collect();
+    DoubleMetaphoneEncoder::new(value).encode()
+}
+
+/// Compute the Kölner Phonetik (Cologne phonetics) code of a german string
+///
+/// Each word is encoded separately, and codes are separated by spaces.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::phonetic::cologne_phonetics;
+///
+/// assert_eq!("3412", cologne_phonetics("Wikipedia"));
+/// assert_eq!("657 52682", cologne_phonetics("Müller-Lüdenscheidt"));
+/// assert_eq!(cologne_phonetics("Meyer"), cologne_phonetics("Maier"));
+/// ```
+pub fn cologne_phonetics(input: &str) -> String {
+    encode_words(input, cologne_phonetics_word)
+}
+
+/// Compute a phonetic key of a french string
+///
+/// The key is built by rewriting groups of letters which sound alike, such as "eau", "au" and
+/// "o", or "ph" and "f", by dropping silent letters, and by merging repeated letters. Nasal
+/// vowels are encoded with digits. Each word is encoded separately, and keys are separated by
+/// spaces.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::phonetic::french_phonetic_key;
+///
+/// assert_eq!(french_phonetic_key("Rousseau"), french_phonetic_key("Roussot"));
+/// assert_eq!(french_phonetic_key("Philippe"), french_phonetic_key("Filip"));
+/// ```
+pub fn french_phonetic_key(input: &str) -> String {
+    encode_words(input, |word| apply_phonetic_rules(word, FRENCH_RULES, "SDTX"))
+}
+
+/// Compute a phonetic key of a spanish string
+///
+/// The key is built by rewriting letters which sound alike, such as "v" and "b", "ll" and "y",
+/// or "z" and soft "c" (seseo), by dropping the silent "h", and by merging repeated letters.
+/// Each word is encoded separately, and keys are separated by spaces.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::phonetic::spanish_phonetic_key;
+///
+/// assert_eq!(spanish_phonetic_key("Valle"), spanish_phonetic_key("baye"));
+/// assert_eq!(spanish_phonetic_key("Cecilia"), spanish_phonetic_key("Sesilia"));
+/// ```
+pub fn spanish_phonetic_key(input: &str) -> String {
+    encode_words(input, |word| apply_phonetic_rules(word, SPANISH_RULES, ""))
+}
+
+fn encode_words<F: Fn(&[char]) -> String>(input: &str, encode: F) -> String {
+    let normalized = remove_diacritics(input).to_uppercase();
+    normalized
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .map(|word| encode(&word.chars().collect::<Vec<_>>()))
+        .filter(|code| !code.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn cologne_phonetics_word(word: &[char]) -> String {
+    let char_at = |index: usize| word.get(index).cloned().unwrap_or('\0');
+    let mut codes = Vec::with_capacity(word.len());
+    for (index, c) in word.iter().enumerate() {
+        let previous = if index > 0 { char_at(index - 1) } else { '\0' };
+        let next = char_at(index + 1);
+        let code: &str = match c {
+            'A' | 'E' | 'I' | 'J' | 'O' | 'U' | 'Y' => "0",
+            'H' => "",
+            'B' => "1",
+            'P' if next == 'H' => "3",
+            'P' => "1",
+            'D' | 'T' if "CSZ".contains(next) => "8",
+            'D' | 'T' => "2",
+            'F' | 'V' | 'W' => "3",
+            'G' | 'K' | 'Q' => "4",
+            'C' if index == 0 && "AHKLOQRUX".contains(next) => "4",
+            'C' if index > 0 && "AHKOQUX".contains(next) && !"SZ".contains(previous) => "4",
+            'C' => "8",
+            'X' if "CKQ".contains(previous) => "8",
+            'X' => "48",
+            'L' => "5",
+            'M' | 'N' => "6",
+            'R' => "7",
+            'S' | 'Z' | 'ß' => "8",
+            _ => "",
+        };
+        codes.push(code);
+    }
+
+    // Repeated codes are merged and zeros are removed, except at the beginning
+    let mut result = String::new();
+    let mut last_code = None;
+    for (index, code) in codes.into_iter().enumerate() {
+        if code.is_empty() {
+            last_code = None;
+            continue;
+        }
+        for c in code.chars() {
+            if last_code != Some(c) && (c != '0' || index == 0) {
+                result.push(c);
+            }
+            last_code = Some(c);
+        }
+    }
+    result
+}
+
+#[derive(Clone, Copy)]
+enum Context {
+    Anywhere,
+    End,
+    BeforeFrontVowel,
+    BetweenVowels,
+    /// Followed by a consonant other than "n", "m" and "h", or by the end of the word
+    Nasal,
+}
+
+type PhoneticRule = (&'static str, &'static str, Context);
+
+/// Rules are tried in order at each position, so longer patterns must come first
+const FRENCH_RULES: &[PhoneticRule] = &[
+    ("EAUX", "O", Context::End),
+    ("EAU", "O", Context::Anywhere),
+    ("AUX", "O", Context::End),
+    ("AU", "O", Context::Anywhere),
+    ("AIN", "2", Context::Nasal),
+    ("EIN", "2", Context::Nasal),
+    ("AI", "E", Context::Anywhere),
+    ("EI", "E", Context::Anywhere),
+    ("OU", "U", Context::Anywhere),
+    ("OI", "OA", Context::Anywhere),
+    ("ER", "E", Context::End),
+    ("EZ", "E", Context::End),
+    ("ET", "E", Context::End),
+    ("AN", "1", Context::Nasal),
+    ("AM", "1", Context::Nasal),
+    ("EN", "1", Context::Nasal),
+    ("EM", "1", Context::Nasal),
+    ("IN", "2", Context::Nasal),
+    ("IM", "2", Context::Nasal),
+    ("UN", "2", Context::Nasal),
+    ("ON", "3", Context::Nasal),
+    ("OM", "3", Context::Nasal),
+    ("SCH", "X", Context::Anywhere),
+    ("CH", "X", Context::Anywhere),
+    ("SH", "X", Context::Anywhere),
+    ("PH", "F", Context::Anywhere),
+    ("TH", "T", Context::Anywhere),
+    ("GN", "NI", Context::Anywhere),
+    ("GUE", "GE", Context::Anywhere),
+    ("GUI", "GI", Context::Anywhere),
+    ("QU", "K", Context::Anywhere),
+    ("Q", "K", Context::Anywhere),
+    ("CK", "K", Context::Anywhere),
+    ("C", "S", Context::BeforeFrontVowel),
+    ("C", "K", Context::Anywhere),
+    ("G", "J", Context::BeforeFrontVowel),
+    ("X", "KS", Context::Anywhere),
+    ("W", "V", Context::Anywhere),
+    ("Z", "S", Context::Anywhere),
+    ("Y", "I", Context::Anywhere),
+    ("S", "Z", Context::BetweenVowels),
+    ("H", "", Context::Anywhere),
+];
+
+/// Rules are tried in order at each position, so longer patterns must come first
+const SPANISH_RULES: &[PhoneticRule] = &[
+    ("LL", "Y", Context::Anywhere),
+    ("CH", "X", Context::Anywhere),
+    ("QU", "K", Context::Anywhere),
+    ("GUE", "GE", Context::Anywhere),
+    ("GUI", "GI", Context::Anywhere),
+    ("Q", "K", Context::Anywhere),
+    ("C", "S", Context::BeforeFrontVowel),
+    ("C", "K", Context::Anywhere),
+    ("G", "J", Context::BeforeFrontVowel),
+    ("Z", "S", Context::Anywhere),
+    ("V", "B", Context::Anywhere),
+    ("W", "U", Context::Anywhere),
+    ("X", "KS", Context::Anywhere),
+    ("Y", "I", Context::End),
+    ("H", "", Context::Anywhere),
+];
+
+fn is_vowel(c: char) -> bool {
+    "AEIOUY".contains(c)
+}
+
+fn apply_phonetic_rules(word: &[char], rules: &[PhoneticRule], silent_endings: &str) -> String {
+    let matches_at = |index: usize, pattern: &str| {
+        let pattern_length = pattern.chars().count();
+        index + pattern_length <= word.len()
+            && word[index..index + pattern_length].iter().cloned().eq(pattern.chars())
+    };
+    let mut rewritten = String::with_capacity(word.len());
+    let mut index = 0;
+    while index < word.len() {
+        let rule = rules.iter().find(|(pattern, _, context)| {
+            if !matches_at(index, pattern) {
+                return false;
+            }
+            let end = index + pattern.chars().count();
+            let next = word.get(end).cloned();
+            match context {
+                Context::Anywhere => true,
+                Context::End => next.is_none(),
+                Context::BeforeFrontVowel => next.map(|c| "EIY".contains(c)).unwrap_or(false),
+                Context::BetweenVowels => {
+                    index > 0 && is_vowel(word[index - 1]) && next.map(is_vowel).unwrap_or(false)
+                }
+                Context::Nasal => next.map(|c| !is_vowel(c) && !"NMH".contains(c)).unwrap_or(true),
+            }
+        });
+        match rule {
+            Some((pattern, replacement, _)) => {
+                rewritten.push_str(replacement);
+                index += pattern.chars().count();
+            }
+            None => {
+                rewritten.push(word[index]);
+                index += 1;
+            }
+        }
+    }
+
+    let mut key: Vec<char> = rewritten.chars().collect();
+    key.dedup();
+    while key.len() > 1 && silent_endings.contains(key[key.len() - 1]) {
+        key.pop();
+    }
+    if key.len() > 1 && !silent_endings.is_empty() && key[key.len() - 1] == 'E' {
+        key.pop();
+    }
+    key.into_iter().collect()
+}
+
+/// Port of the reference Double Metaphone implementation
+///
+/// Characters are read with `char_at` and `contains`, which handle out of bounds indexes like the
+/// reference implementation, so that rules can be transcribed as is.
+struct DoubleMetaphoneEncoder {
+    value: Vec<char>,
+    primary: String,
+    alternate: String,
+    slavo_germanic: bool,
+}
+
+impl DoubleMetaphoneEncoder {
+    fn new(value: Vec<char>) -> Self {
+        let as_string: String = value.iter().collect();
+        let slavo_germanic =
+            as_string.contains('W') || as_string.contains('K') || as_string.contains("CZ");
+        DoubleMetaphoneEncoder {
+            value,
+            primary: String::new(),
+            alternate: String::new(),
+            slavo_germanic,
+        }
+    }
+
+    fn encode(mut self) -> DoubleMetaphone {
+        let is_silent_start = ["GN", "KN", "PN", "WR", "PS"]
+            .iter()
+            .any(|start| self.contains(0, 2, &[start]));
+        let mut index = if is_silent_start { 1 } else { 0 };
+        while !self.is_complete() && index < self.value.len() {
+            index = match self.value[index] {
+                'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                    if index == 0 {
+                        self.append('A');
+                    }
+                    index + 1
+                }
+                'B' => {
+                    self.append('P');
+                    self.skip_double(index, 'B')
+                }
+                'C' => self.handle_c(index),
+                'D' => self.handle_d(index),
+                'F' => {
+                    self.append('F');
+                    self.skip_double(index, 'F')
+                }
+                'G' => self.handle_g(index),
+                'H' => self.handle_h(index),
+                'J' => self.handle_j(index),
+                'K' => {
+                    self.append('K');
+                    self.skip_double(index, 'K')
+                }
+                'L' => self.handle_l(index),
+                'M' => {
+                    self.append('M');
+                    if self.condition_m0(index) {
+                        index + 2
+                    } else {
+                        index + 1
+                    }
+                }
+                'N' => {
+                    self.append('N');
+                    self.skip_double(index, 'N')
+                }
+                'P' => self.handle_p(index),
+                'Q' => {
+                    self.append('K');
+                    self.skip_double(index, 'Q')
+                }
+                'R' => self.handle_r(index),
+                'S' => self.handle_s(index),
+                'T' => self.handle_t(index),
+                'V' => {
+                    self.append('F');
+                    self.skip_double(index, 'V')
+                }
+                'W' => self.handle_w(index),
+                'X' => self.handle_x(index),
+                'Z' => self.handle_z(index),
+                _ => index + 1,
+            };
+        }
+        DoubleMetaphone {
+            primary: self.primary,
+            alternate: self.alternate,
+        }
+    }
+
+    fn char_at(&self, index: isize) -> char {
+        if index < 0 {
+            return '\0';
+        }
+        self.value.get(index as usize).cloned().unwrap_or('\0')
+    }
+
+    fn contains(&self, start: isize, length: usize, criteria: &[&str]) -> bool {
+        if start < 0 || start as usize + length > self.value.len() {
+            return false;
+        }
+        let start = start as usize;
+        let target = &self.value[start..start + length];
+        criteria
+            .iter()
+            .any(|criterion| target.iter().cloned().eq(criterion.chars()))
+    }
+
+    fn is_vowel_at(&self, index: isize) -> bool {
+        is_vowel(self.char_at(index))
+    }
+
+    fn last_index(&self) -> usize {
+        self.value.len() - 1
+    }
+
+    fn is_complete(&self) -> bool {
+        self.primary.len() >= DOUBLE_METAPHONE_MAX_LENGTH
+            && self.alternate.len() >= DOUBLE_METAPHONE_MAX_LENGTH
+    }
+
+    fn append(&mut self, code: char) {
+        self.append_primary(code);
+        self.append_alternate(code);
+    }
+
+    fn append_both(&mut self, primary: &str, alternate: &str) {
+        for c in primary.chars() {
+            self.append_primary(c);
+        }
+        for c in alternate.chars() {
+            self.append_alternate(c);
+        }
+    }
+
+    fn append_primary(&mut self, code: char) {
+        if self.primary.len() < DOUBLE_METAPHONE_MAX_LENGTH {
+            self.primary.push(code);
+        }
+    }
+
+    fn append_alternate(&mut self, code: char) {
+        if self.alternate.len() < DOUBLE_METAPHONE_MAX_LENGTH {
+            self.alternate.push(code);
+        }
+    }
+
+    fn skip_double(&self, index: usize, c: char) -> usize {
+        if self.char_at(index as isize + 1) == c {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_c(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.condition_c0(i) {
+            self.append('K');
+            index + 2
+        } else if index == 0 && self.contains(i, 6, &["CAESAR"]) {
+            self.append('S');
+            index + 2
+        } else if self.contains(i, 2, &["CH"]) {
+            self.handle_ch(index)
+        } else if self.contains(i, 2, &["CZ"]) && !self.contains(i - 2, 4, &["WICZ"]) {
+            // "Czerny"
+            self.append_both("S", "X");
+            index + 2
+        } else if self.contains(i + 1, 3, &["CIA"]) {
+            // "focaccia"
+            self.append('X');
+            index + 3
+        } else if self.contains(i, 2, &["CC"]) && !(index == 1 && self.char_at(0) == 'M') {
+            // Double "cc", but not "McClelland"
+            self.handle_cc(index)
+        } else if self.contains(i, 2, &["CK", "CG", "CQ"]) {
+            self.append('K');
+            index + 2
+        } else if self.contains(i, 2, &["CI", "CE", "CY"]) {
+            // Italian vs. English
+            if self.contains(i, 3, &["CIO", "CIE", "CIA"]) {
+                self.append_both("S", "X");
+            } else {
+                self.append('S');
+            }
+            index + 2
+        } else {
+            self.append('K');
+            if self.contains(i + 1, 2, &[" C", " Q", " G"]) {
+                // "Mac Caffrey", "Mac Gregor"
+                index + 3
+            } else if self.contains(i + 1, 1, &["C", "K", "Q"])
+                && !self.contains(i + 1, 2, &["CE", "CI"])
+            {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_cc(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.contains(i + 2, 1, &["I", "E", "H"]) && !self.contains(i + 2, 2, &["HU"]) {
+            // "bellocchio", but not "bacchus"
+            if (index == 1 && self.char_at(i - 1) == 'A')
+                || self.contains(i - 1, 5, &["UCCEE", "UCCES"])
+            {
+                // "accident", "accede", "succeed"
+                self.append_both("KS", "KS");
+            } else {
+                // "bacci", "bertucci", other italian
+                self.append('X');
+            }
+            index + 3
+        } else {
+            // Pierce's rule
+            self.append('K');
+            index + 2
+        }
+    }
+
+    fn handle_ch(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if index > 0 && self.contains(i, 4, &["CHAE"]) {
+            // "Michael"
+            self.append_both("K", "X");
+        } else if self.condition_ch0(i) || self.condition_ch1(i) {
+            // Greek roots, such as "chemistry", or germanic "ch" for "kh" sound
+            self.append('K');
+        } else if index > 0 {
+            if self.contains(0, 2, &["MC"]) {
+                self.append('K');
+            } else {
+                self.append_both("X", "K");
+            }
+        } else {
+            self.append('X');
+        }
+        index + 2
+    }
+
+    fn handle_d(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.contains(i, 2, &["DG"]) {
+            if self.contains(i + 2, 1, &["I", "E", "Y"]) {
+                // "edge"
+                self.append('J');
+                index + 3
+            } else {
+                // "edgar"
+                self.append_both("TK", "TK");
+                index + 2
+            }
+        } else if self.contains(i, 2, &["DT", "DD"]) {
+            self.append('T');
+            index + 2
+        } else {
+            self.append('T');
+            index + 1
+        }
+    }
+
+    fn handle_g(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        let slavo_germanic = self.slavo_germanic;
+        if self.char_at(i + 1) == 'H' {
+            self.handle_gh(index)
+        } else if self.char_at(i + 1) == 'N' {
+            if index == 1 && self.is_vowel_at(0) && !slavo_germanic {
+                self.append_both("KN", "N");
+            } else if !self.contains(i + 2, 2, &["EY"])
+                && self.char_at(i + 1) != 'Y'
+                && !slavo_germanic
+            {
+                self.append_both("N", "KN");
+            } else {
+                self.append_both("KN", "KN");
+            }
+            index + 2
+        } else if self.contains(i + 1, 2, &["LI"]) && !slavo_germanic {
+            self.append_both("KL", "L");
+            index + 2
+        } else if index == 0
+            && (self.char_at(i + 1) == 'Y'
+                || self.contains(
+                    i + 1,
+                    2,
+                    &["ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER"],
+                ))
+        {
+            // "-ges-", "-gep-", "-gel-", "-gie-" at the beginning
+            self.append_both("K", "J");
+            index + 2
+        } else if (self.contains(i + 1, 2, &["ER"]) || self.char_at(i + 1) == 'Y')
+            && !self.contains(0, 6, &["DANGER", "RANGER", "MANGER"])
+            && !self.contains(i - 1, 1, &["E", "I"])
+            && !self.contains(i - 1, 3, &["RGY", "OGY"])
+        {
+            // "-ger-", "-gy-"
+            self.append_both("K", "J");
+            index + 2
+        } else if self.contains(i + 1, 1, &["E", "I", "Y"])
+            || self.contains(i - 1, 4, &["AGGI", "OGGI"])
+        {
+            // Italian "biaggi"
+            if self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+                || self.contains(i + 1, 2, &["ET"])
+            {
+                // Obvious germanic
+                self.append('K');
+            } else if self.contains(i + 1, 3, &["IER"]) {
+                self.append('J');
+            } else {
+                self.append_both("J", "K");
+            }
+            index + 2
+        } else if self.char_at(i + 1) == 'G' {
+            self.append('K');
+            index + 2
+        } else {
+            self.append('K');
+            index + 1
+        }
+    }
+
+    fn handle_gh(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if index > 0 && !self.is_vowel_at(i - 1) {
+            self.append('K');
+        } else if index == 0 {
+            if self.char_at(i + 2) == 'I' {
+                self.append('J');
+            } else {
+                self.append('K');
+            }
+        } else if (index > 1 && self.contains(i - 2, 1, &["B", "H", "D"]))
+            || (index > 2 && self.contains(i - 3, 1, &["B", "H", "D"]))
+            || (index > 3 && self.contains(i - 4, 1, &["B", "H"]))
+        {
+            // Parker's rule, with some further refinements: "hugh"
+        } else if index > 2
+            && self.char_at(i - 1) == 'U'
+            && self.contains(i - 3, 1, &["C", "G", "L", "R", "T"])
+        {
+            // "laugh", "McLaughlin", "cough", "gough", "rough", "tough"
+            self.append('F');
+        } else if self.char_at(i - 1) != 'I' {
+            self.append('K');
+        }
+        index + 2
+    }
+
+    fn handle_h(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        // Only keep if first and before a vowel, or between two vowels
+        if (index == 0 || self.is_vowel_at(i - 1)) && self.is_vowel_at(i + 1) {
+            self.append('H');
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_j(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.contains(i, 4, &["JOSE"]) || self.contains(0, 4, &["SAN "]) {
+            // Obvious spanish, "Jose", "San Jacinto"
+            if (index == 0 && self.char_at(i + 4) == ' ')
+                || self.value.len() == 4
+                || self.contains(0, 4, &["SAN "])
+            {
+                self.append('H');
+            } else {
+                self.append_both("J", "H");
+            }
+            return index + 1;
+        }
+        if index == 0 {
+            self.append_both("J", "A");
+        } else if self.is_vowel_at(i - 1)
+            && !self.slavo_germanic
+            && (self.char_at(i + 1) == 'A' || self.char_at(i + 1) == 'O')
+        {
+            self.append_both("J", "H");
+        } else if index == self.last_index() {
+            self.append_primary('J');
+        } else if !self.contains(i + 1, 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+            && !self.contains(i - 1, 1, &["S", "K", "L"])
+        {
+            self.append('J');
+        }
+        self.skip_double(index, 'J')
+    }
+
+    fn handle_l(&mut self, index: usize) -> usize {
+        if self.char_at(index as isize + 1) == 'L' {
+            if self.condition_l0(index as isize) {
+                self.append_primary('L');
+            } else {
+                self.append('L');
+            }
+            index + 2
+        } else {
+            self.append('L');
+            index + 1
+        }
+    }
+
+    fn handle_p(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.char_at(i + 1) == 'H' {
+            self.append('F');
+            index + 2
+        } else {
+            self.append('P');
+            if self.contains(i + 1, 1, &["P", "B"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_r(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if index == self.last_index()
+            && !self.slavo_germanic
+            && self.contains(i - 2, 2, &["IE"])
+            && !self.contains(i - 4, 2, &["ME", "MA"])
+        {
+            // French, e.g. "Rogier"
+            self.append_alternate('R');
+        } else {
+            self.append('R');
+        }
+        self.skip_double(index, 'R')
+    }
+
+    fn handle_s(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.contains(i - 1, 3, &["ISL", "YSL"]) {
+            // "island", "isle", "carlisle", "carlysle"
+            index + 1
+        } else if index == 0 && self.contains(i, 5, &["SUGAR"]) {
+            self.append_both("X", "S");
+            index + 1
+        } else if self.contains(i, 2, &["SH"]) {
+            if self.contains(i + 1, 4, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                // Germanic
+                self.append('S');
+            } else {
+                self.append('X');
+            }
+            index + 2
+        } else if self.contains(i, 3, &["SIO", "SIA"]) || self.contains(i, 4, &["SIAN"]) {
+            // Italian and armenian
+            if self.slavo_germanic {
+                self.append('S');
+            } else {
+                self.append_both("S", "X");
+            }
+            index + 3
+        } else if (index == 0 && self.contains(i + 1, 1, &["M", "N", "L", "W"]))
+            || self.contains(i + 1, 1, &["Z"])
+        {
+            // German and anglicisations, e.g. "smith" matches "schmidt", and slavic "-sz-"
+            self.append_both("S", "X");
+            if self.contains(i + 1, 1, &["Z"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        } else if self.contains(i, 2, &["SC"]) {
+            self.handle_sc(index)
+        } else {
+            if index == self.last_index() && self.contains(i - 2, 2, &["AI", "OI"]) {
+                // French, e.g. "resnais", "artois"
+                self.append_alternate('S');
+            } else {
+                self.append('S');
+            }
+            if self.contains(i + 1, 1, &["S", "Z"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_sc(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.char_at(i + 2) == 'H' {
+            // Schlesinger's rule
+            if self.contains(i + 3, 2, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                // Dutch origin, e.g. "school", "schooner"
+                if self.contains(i + 3, 2, &["ER", "EN"]) {
+                    // "schermerhorn", "schenker"
+                    self.append_both("X", "SK");
+                } else {
+                    self.append_both("SK", "SK");
+                }
+            } else if index == 0 && !self.is_vowel_at(3) && self.char_at(3) != 'W' {
+                self.append_both("X", "S");
+            } else {
+                self.append('X');
+            }
+        } else if self.contains(i + 2, 1, &["I", "E", "Y"]) {
+            self.append('S');
+        } else {
+            self.append_both("SK", "SK");
+        }
+        index + 3
+    }
+
+    fn handle_t(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.contains(i, 4, &["TION"]) || self.contains(i, 3, &["TIA", "TCH"]) {
+            self.append('X');
+            index + 3
+        } else if self.contains(i, 2, &["TH"]) || self.contains(i, 3, &["TTH"]) {
+            if self.contains(i + 2, 2, &["OM", "AM"])
+                || self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+            {
+                // "thomas", "thames" or germanic
+                self.append('T');
+            } else {
+                self.append('0');
+            }
+            index + 2
+        } else {
+            self.append('T');
+            if self.contains(i + 1, 1, &["T", "D"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_w(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.contains(i, 2, &["WR"]) {
+            self.append('R');
+            index + 2
+        } else if index == 0 && (self.is_vowel_at(i + 1) || self.contains(i, 2, &["WH"])) {
+            if self.is_vowel_at(i + 1) {
+                // "Wasserman" should match "Vasserman"
+                self.append_both("A", "F");
+            } else {
+                // "Uomo" should match "Womo"
+                self.append('A');
+            }
+            index + 1
+        } else if (index == self.last_index() && self.is_vowel_at(i - 1))
+            || self.contains(i - 1, 5, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.contains(0, 3, &["SCH"])
+        {
+            // "Arnow" should match "Arnoff"
+            self.append_alternate('F');
+            index + 1
+        } else if self.contains(i, 4, &["WICZ", "WITZ"]) {
+            // Polish, e.g. "filipowicz"
+            self.append_both("TS", "FX");
+            index + 4
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_x(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if index == 0 {
+            self.append('S');
+            return index + 1;
+        }
+        if !(index == self.last_index()
+            && (self.contains(i - 3, 3, &["IAU", "EAU"]) || self.contains(i - 2, 2, &["AU", "OU"])))
+        {
+            // The final "x" is silent in french, e.g. "breaux"
+            self.append_both("KS", "KS");
+        }
+        if self.contains(i + 1, 1, &["C", "X"]) {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_z(&mut self, index: usize) -> usize {
+        let i = index as isize;
+        if self.char_at(i + 1) == 'H' {
+            // Chinese pinyin, e.g. "zhao"
+            self.append('J');
+            return index + 2;
+        }
+        if self.contains(i + 1, 2, &["ZO", "ZI", "ZA"])
+            || (self.slavo_germanic && index > 0 && self.char_at(i - 1) != 'T')
+        {
+            self.append_both("S", "TS");
+        } else {
+            self.append('S');
+        }
+        self.skip_double(index, 'Z')
+    }
+
+    fn condition_c0(&self, i: isize) -> bool {
+        if self.contains(i, 4, &["CHIA"]) {
+            true
+        } else if i <= 1 || self.is_vowel_at(i - 2) || !self.contains(i - 1, 3, &["ACH"]) {
+            false
+        } else {
+            let c = self.char_at(i + 2);
+            (c != 'I' && c != 'E') || self.contains(i - 2, 6, &["BACHER", "MACHER"])
+        }
+    }
+
+    fn condition_ch0(&self, i: isize) -> bool {
+        i == 0
+            && (self.contains(i + 1, 5, &["HARAC", "HARIS"])
+                || self.contains(i + 1, 3, &["HOR", "HYM", "HIA", "HEM"]))
+            && !self.contains(0, 5, &["CHORE"])
+    }
+
+    fn condition_ch1(&self, i: isize) -> bool {
+        self.contains(0, 4, &["VAN ", "VON "])
+            || self.contains(0, 3, &["SCH"])
+            || self.contains(i - 2, 6, &["ORCHES", "ARCHIT", "ORCHID"])
+            || self.contains(i + 2, 1, &["T", "S"])
+            || ((self.contains(i - 1, 1, &["A", "O", "U", "E"]) || i == 0)
+                && (self.contains(i + 2, 1, &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "])
+                    || i + 1 == self.last_index() as isize))
+    }
+
+    fn condition_l0(&self, i: isize) -> bool {
+        let length = self.value.len() as isize;
+        if i == length - 3 && self.contains(i - 1, 4, &["ILLO", "ILLA", "ALLE"]) {
+            true
+        } else {
+            (self.contains(length - 2, 2, &["AS", "OS"])
+                || self.contains(length - 1, 1, &["A", "O"]))
+                && self.contains(i - 1, 4, &["ALLE"])
+        }
+    }
+
+    fn condition_m0(&self, index: usize) -> bool {
+        let i = index as isize;
+        if self.char_at(i + 1) == 'M' {
+            return true;
+        }
+        self.contains(i - 1, 3, &["UMB"])
+            && (index + 1 == self.last_index() || self.contains(i + 2, 2, &["ER"]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dm(input: &str) -> (String, String) {
+        let codes = double_metaphone(input);
+        (codes.primary, codes.alternate)
+    }
+
+    #[test]
+    fn double_metaphone_works() {
+        let test_cases = vec![
+            ("", "", ""),
+            ("Thompson", "TMPS", "TMPS"),
+            ("Schmidt", "XMT", "SMT"),
+            ("Thumb", "0M", "0M"),
+            ("Caesar", "SSR", "SSR"),
+            ("Michael", "MKL", "MXL"),
+            ("Gnagy", "NK", "NJ"),
+            ("Jose", "HS", "HS"),
+            ("Tchaikovsky", "XKFS", "XKFS"),
+            ("Arnow", "ARN", "ARNF"),
+            ("Filipowicz", "FLPT", "FLPF"),
+            ("Breaux", "PR", "PR"),
+            ("Zhao", "J", "J"),
+            ("Céline", "SLN", "SLN"),
+            ("Xavier", "SF", "SFR"),
+            ("Edge", "AJ", "AJ"),
+            ("laugh", "LF", "LF"),
+        ];
+        for (input, primary, alternate) in test_cases {
+            assert_eq!(
+                (primary.to_string(), alternate.to_string()),
+                dm(input),
+                "input: {}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn cologne_phonetics_works() {
+        assert_eq!("", cologne_phonetics(""));
+        assert_eq!("3412", cologne_phonetics("Wikipedia"));
+        assert_eq!("65752682", cologne_phonetics("Müllerlüdenscheidt"));
+        assert_eq!("657 52682", cologne_phonetics("Müller-Lüdenscheidt"));
+        assert_eq!("17863", cologne_phonetics("Breschnew"));
+        assert_eq!("67", cologne_phonetics("Meyer"));
+        assert_eq!("478", cologne_phonetics("Chris"));
+        assert_eq!("4837", cologne_phonetics("Xaver"));
+        assert_eq!("8278", cologne_phonetics("Straße"));
+    }
+
+    #[test]
+    fn french_phonetic_key_works() {
+        assert_eq!("RUSO", french_phonetic_key("Rousseau"));
+        assert_eq!("RUSO", french_phonetic_key("Roussot"));
+        assert_eq!("RUZO", french_phonetic_key("Rouseaux"));
+        assert_eq!("FILIP", french_phonetic_key("Philippe"));
+        assert_eq!("FILIP", french_phonetic_key("Filip"));
+        assert_eq!("SELIN", french_phonetic_key("Céline"));
+        assert_eq!("DUP3", french_phonetic_key("Dupont"));
+        assert_eq!("V2S1", french_phonetic_key("Vincent"));
+        assert_eq!("GILOM", french_phonetic_key("Guillaume"));
+        assert_eq!("MILEN FARM", french_phonetic_key("Mylène Farmer"));
+    }
+
+    #[test]
+    fn spanish_phonetic_key_works() {
+        assert_eq!("BAYE", spanish_phonetic_key("Valle"));
+        assert_eq!("SESILIA", spanish_phonetic_key("Cecilia"));
+        assert_eq!("KESO", spanish_phonetic_key("queso"));
+        assert_eq!("JIMENES", spanish_phonetic_key("Jiménez"));
+        assert_eq!("ORASIO", spanish_phonetic_key("Horacio"));
+        assert_eq!("JOSE MARIA", spanish_phonetic_key("José-María"));
+    }
+
+    #[test]
+    fn phonetic_key_works() {
+        assert_eq!(Some("SM0".to_string()), phonetic_key("Smith", Language::EN));
+        assert_eq!(Some("67".to_string()), phonetic_key("Maier", Language::DE));
+        assert_eq!(None, phonetic_key("Maier", Language::JA));
+    }
+}
@@ -1,9 +1,509 @@
+use std::cmp::{max, min, Ordering};
+use std::iter::FromIterator;
 use std::ops;
 
 pub fn ranges_overlap(r1: &ops::Range<usize>, r2: &ops::Range<usize>) -> bool {
     r1.start < r2.end && r1.end > r2.start
 }
 
+/// Whether `outer` contains `inner`, an empty range being contained in any range which contains
+/// its position
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::range::contains;
+///
+/// assert!(contains(&(3..8), &(4..8)));
+/// assert!(!contains(&(3..8), &(4..9)));
+/// ```
+pub fn contains(outer: &ops::Range<usize>, inner: &ops::Range<usize>) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+/// Intersection of two ranges, or `None` when they do not overlap
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::range::intersection;
+///
+/// assert_eq!(Some(4..6), intersection(&(3..6), &(4..7)));
+/// assert_eq!(None, intersection(&(3..6), &(6..7)));
+/// ```
+pub fn intersection(r1: &ops::Range<usize>, r2: &ops::Range<usize>) -> Option<ops::Range<usize>> {
+    if ranges_overlap(r1, r2) {
+        Some(max(r1.start, r2.start)..min(r1.end, r2.end))
+    } else {
+        None
+    }
+}
+
+/// Union of two ranges, or `None` when they are neither overlapping nor adjacent, in which case
+/// their union is not a range
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::range::union;
+///
+/// assert_eq!(Some(3..7), union(&(3..6), &(6..7)));
+/// assert_eq!(None, union(&(3..6), &(7..9)));
+/// ```
+pub fn union(r1: &ops::Range<usize>, r2: &ops::Range<usize>) -> Option<ops::Range<usize>> {
+    if r1.start <= r2.end && r2.start <= r1.end {
+        Some(min(r1.start, r2.start)..max(r1.end, r2.end))
+    } else {
+        None
+    }
+}
+
+/// Merge the overlapping ranges, and return the resulting ranges sorted by start
+///
+/// Adjacent ranges, such as `3..6` and `6..7`, are not merged, and empty ranges are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::range::merge_overlapping;
+///
+/// assert_eq!(vec![1..4, 4..9], merge_overlapping(&[5..9, 1..3, 2..4, 4..6]));
+/// ```
+pub fn merge_overlapping(ranges: &[ops::Range<usize>]) -> Vec<ops::Range<usize>> {
+    let mut sorted_ranges: Vec<ops::Range<usize>> = ranges
+        .iter()
+        .filter(|range| range.start < range.end)
+        .cloned()
+        .collect();
+    sorted_ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<ops::Range<usize>> = Vec::with_capacity(sorted_ranges.len());
+    for range in sorted_ranges {
+        match merged.last_mut() {
+            Some(last) if ranges_overlap(last, &range) => last.end = max(last.end, range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Remove `r2` from `r1`, which gives zero, one or two non-empty ranges
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::range::subtract;
+///
+/// assert_eq!(vec![0..3, 5..8], subtract(&(0..8), &(3..5)));
+/// assert_eq!(vec![0..3], subtract(&(0..8), &(3..10)));
+/// assert!(subtract(&(3..5), &(0..8)).is_empty());
+/// ```
+pub fn subtract(r1: &ops::Range<usize>, r2: &ops::Range<usize>) -> Vec<ops::Range<usize>> {
+    if !ranges_overlap(r1, r2) {
+        return if r1.start < r1.end {
+            vec![r1.clone()]
+        } else {
+            vec![]
+        };
+    }
+    vec![r1.start..r2.start, r2.end..r1.end]
+        .into_iter()
+        .filter(|range| range.start < range.end)
+        .collect()
+}
+
+/// Number of positions between two ranges, which is 0 when they overlap or are adjacent
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::range::distance;
+///
+/// assert_eq!(2, distance(&(3..6), &(8..9)));
+/// assert_eq!(0, distance(&(3..6), &(6..9)));
+/// ```
+pub fn distance(r1: &ops::Range<usize>, r2: &ops::Range<usize>) -> usize {
+    max(
+        r2.start.saturating_sub(r1.end),
+        r1.start.saturating_sub(r2.end),
+    )
+}
+
+/// Candidate span, along with its score, to be used with `resolve_overlaps`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoredRange {
+    pub range: ops::Range<usize>,
+    pub score: f64,
+}
+
+/// Strategy used to select a non-overlapping subset of candidate spans
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlapStrategy {
+    /// Greedily keep the longest spans first
+    LongestFirst,
+    /// Scan spans from left to right, keeping the longest span at each position
+    LeftmostLongest,
+    /// Greedily keep the spans with the highest score first
+    HighestScore,
+    /// Keep the subset of spans which maximizes the sum of scores, using weighted interval
+    /// scheduling
+    OptimalWeighted,
+}
+
+/// Select a non-overlapping subset of the candidates according to the strategy, and return the
+/// indexes of the selected candidates, sorted by range start
+///
+/// Remaining ties are broken by preferring higher scores, then longer spans, then spans
+/// starting first.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::range::{resolve_overlaps, OverlapStrategy, ScoredRange};
+///
+/// let candidates = vec![
+///     ScoredRange { range: 0..5, score: 0.9 },
+///     ScoredRange { range: 3..12, score: 0.5 },
+///     ScoredRange { range: 6..12, score: 0.8 },
+/// ];
+/// assert_eq!(vec![1], resolve_overlaps(&candidates, OverlapStrategy::LongestFirst));
+/// assert_eq!(vec![0, 2], resolve_overlaps(&candidates, OverlapStrategy::LeftmostLongest));
+/// assert_eq!(vec![0, 2], resolve_overlaps(&candidates, OverlapStrategy::HighestScore));
+/// assert_eq!(vec![0, 2], resolve_overlaps(&candidates, OverlapStrategy::OptimalWeighted));
+/// ```
+pub fn resolve_overlaps(candidates: &[ScoredRange], strategy: OverlapStrategy) -> Vec<usize> {
+    let start = |index: usize| candidates[index].range.start;
+    let length = |index: usize| candidates[index].range.len();
+    let score = |index: usize| candidates[index].score;
+    let by_score = |lhs: usize, rhs: usize| {
+        score(rhs)
+            .partial_cmp(&score(lhs))
+            .unwrap_or(Ordering::Equal)
+    };
+    let mut indexes: Vec<usize> = (0..candidates.len()).collect();
+    match strategy {
+        OverlapStrategy::LongestFirst => {
+            indexes.sort_by(|&lhs, &rhs| {
+                length(rhs)
+                    .cmp(&length(lhs))
+                    .then_with(|| by_score(lhs, rhs))
+                    .then_with(|| start(lhs).cmp(&start(rhs)))
+            });
+            select_greedily(candidates, indexes)
+        }
+        OverlapStrategy::HighestScore => {
+            indexes.sort_by(|&lhs, &rhs| {
+                by_score(lhs, rhs)
+                    .then_with(|| length(rhs).cmp(&length(lhs)))
+                    .then_with(|| start(lhs).cmp(&start(rhs)))
+            });
+            select_greedily(candidates, indexes)
+        }
+        OverlapStrategy::LeftmostLongest => {
+            indexes.sort_by(|&lhs, &rhs| {
+                start(lhs)
+                    .cmp(&start(rhs))
+                    .then_with(|| length(rhs).cmp(&length(lhs)))
+                    .then_with(|| by_score(lhs, rhs))
+            });
+            select_greedily(candidates, indexes)
+        }
+        OverlapStrategy::OptimalWeighted => select_optimally(candidates),
+    }
+}
+
+/// Keep the candidates in the given order, as long as they do not overlap with the ones
+/// already kept
+fn select_greedily(candidates: &[ScoredRange], ordered_indexes: Vec<usize>) -> Vec<usize> {
+    // Kept candidates, sorted by range start
+    let mut selected: Vec<usize> = Vec::new();
+    for index in ordered_indexes {
+        let range = &candidates[index].range;
+        let position = selected.partition_point(|i| candidates[*i].range.start < range.start);
+        let overlaps_previous =
+            position > 0 && ranges_overlap(&candidates[selected[position - 1]].range, range);
+        let overlaps_next = selected
+            .get(position)
+            .map(|i| ranges_overlap(&candidates[*i].range, range))
+            .unwrap_or(false);
+        if !overlaps_previous && !overlaps_next {
+            selected.insert(position, index);
+        }
+    }
+    selected
+}
+
+/// Weighted interval scheduling, which finds the non-overlapping subset of candidates with the
+/// highest total score in O(n log n)
+fn select_optimally(candidates: &[ScoredRange]) -> Vec<usize> {
+    let mut indexes: Vec<usize> = (0..candidates.len()).collect();
+    indexes.sort_by_key(|i| (candidates[*i].range.end, candidates[*i].range.start));
+    // best_scores[k] is the best total score using the first k candidates sorted by end
+    let mut best_scores: Vec<f64> = vec![0.0; indexes.len() + 1];
+    let mut previous_compatible: Vec<usize> = Vec::with_capacity(indexes.len());
+    for (k, index) in indexes.iter().enumerate() {
+        let range = &candidates[*index].range;
+        // Number of candidates, among the first k, which end before this one starts
+        let compatible = indexes[..k].partition_point(|i| candidates[*i].range.end <= range.start);
+        previous_compatible.push(compatible);
+        let with_candidate = best_scores[compatible] + candidates[*index].score;
+        best_scores[k + 1] = best_scores[k].max(with_candidate);
+    }
+    let mut selected = Vec::new();
+    let mut k = indexes.len();
+    while k > 0 {
+        let with_candidate =
+            best_scores[previous_compatible[k - 1]] + candidates[indexes[k - 1]].score;
+        if with_candidate >= best_scores[k - 1] {
+            selected.push(indexes[k - 1]);
+            k = previous_compatible[k - 1];
+        } else {
+            k -= 1;
+        }
+    }
+    selected.reverse();
+    selected
+}
+
+/// Interval tree mapping spans to values, which finds the spans overlapping, containing or
+/// contained by a range in O(log n + k), k being the number of results
+///
+/// The tree is an AVL tree ordered by span start and end, in which each node also stores the
+/// maximum end of its subtree. Results are returned sorted by span start and end.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::range::IntervalTree;
+///
+/// let mut tree = IntervalTree::new();
+/// tree.insert(0..8, "new york");
+/// tree.insert(4..8, "york");
+/// tree.insert(12..18, "boston");
+///
+/// let overlapping: Vec<&str> = tree.overlapping(&(6..14)).into_iter().map(|(_, v)| *v).collect();
+/// assert_eq!(vec!["new york", "york", "boston"], overlapping);
+/// assert_eq!(vec![(&(0..8), &"new york")], tree.containing(&(2..6)));
+/// assert_eq!(vec![(&(12..18), &"boston")], tree.contained_in(&(10..20)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct IntervalTree<T> {
+    root: Option<Box<IntervalNode<T>>>,
+    len: usize,
+}
+
+#[derive(Debug, Clone)]
+struct IntervalNode<T> {
+    range: ops::Range<usize>,
+    value: T,
+    max_end: usize,
+    height: usize,
+    left: Option<Box<IntervalNode<T>>>,
+    right: Option<Box<IntervalNode<T>>>,
+}
+
+#[derive(Clone, Copy)]
+enum IntervalRelation {
+    Overlapping,
+    Containing,
+    ContainedIn,
+}
+
+impl<T> IntervalTree<T> {
+    pub fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Insert a span in O(log n), identical spans being all kept
+    pub fn insert(&mut self, range: ops::Range<usize>, value: T) {
+        self.root = Some(insert_node(self.root.take(), range, value));
+        self.len += 1;
+    }
+
+    /// Spans overlapping the range, in the sense of `ranges_overlap`
+    pub fn overlapping(&self, range: &ops::Range<usize>) -> Vec<(&ops::Range<usize>, &T)> {
+        self.search(range, IntervalRelation::Overlapping)
+    }
+
+    /// Spans containing the range, in the sense of `contains`
+    pub fn containing(&self, range: &ops::Range<usize>) -> Vec<(&ops::Range<usize>, &T)> {
+        self.search(range, IntervalRelation::Containing)
+    }
+
+    /// Spans contained by the range, in the sense of `contains`
+    pub fn contained_in(&self, range: &ops::Range<usize>) -> Vec<(&ops::Range<usize>, &T)> {
+        self.search(range, IntervalRelation::ContainedIn)
+    }
+
+    fn search(
+        &self,
+        range: &ops::Range<usize>,
+        relation: IntervalRelation,
+    ) -> Vec<(&ops::Range<usize>, &T)> {
+        let mut results = Vec::new();
+        search_nodes(&self.root, range, relation, &mut results);
+        results
+    }
+}
+
+impl<T> Default for IntervalTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<(ops::Range<usize>, T)> for IntervalTree<T> {
+    fn from_iter<I: IntoIterator<Item = (ops::Range<usize>, T)>>(iter: I) -> Self {
+        let mut tree = Self::new();
+        for (range, value) in iter {
+            tree.insert(range, value);
+        }
+        tree
+    }
+}
+
+impl<T> IntervalNode<T> {
+    fn update(&mut self) {
+        self.height = 1 + max(node_height(&self.left), node_height(&self.right));
+        self.max_end = max(
+            self.range.end,
+            max(node_max_end(&self.left), node_max_end(&self.right)),
+        );
+    }
+}
+
+fn node_height<T>(node: &Option<Box<IntervalNode<T>>>) -> usize {
+    node.as_ref().map(|node| node.height).unwrap_or(0)
+}
+
+fn node_max_end<T>(node: &Option<Box<IntervalNode<T>>>) -> usize {
+    node.as_ref().map(|node| node.max_end).unwrap_or(0)
+}
+
+fn insert_node<T>(
+    node: Option<Box<IntervalNode<T>>>,
+    range: ops::Range<usize>,
+    value: T,
+) -> Box<IntervalNode<T>> {
+    let mut node = match node {
+        Some(node) => node,
+        None => {
+            return Box::new(IntervalNode {
+                max_end: range.end,
+                range,
+                value,
+                height: 1,
+                left: None,
+                right: None,
+            })
+        }
+    };
+    if (range.start, range.end) < (node.range.start, node.range.end) {
+        node.left = Some(insert_node(node.left.take(), range, value));
+    } else {
+        node.right = Some(insert_node(node.right.take(), range, value));
+    }
+    rebalance(node)
+}
+
+fn rebalance<T>(mut node: Box<IntervalNode<T>>) -> Box<IntervalNode<T>> {
+    node.update();
+    let left_height = node_height(&node.left);
+    let right_height = node_height(&node.right);
+    if left_height > right_height + 1 {
+        let left = node.left.take().unwrap();
+        node.left = Some(if node_height(&left.left) < node_height(&left.right) {
+            rotate_left(left)
+        } else {
+            left
+        });
+        rotate_right(node)
+    } else if right_height > left_height + 1 {
+        let right = node.right.take().unwrap();
+        node.right = Some(if node_height(&right.right) < node_height(&right.left) {
+            rotate_right(right)
+        } else {
+            right
+        });
+        rotate_left(node)
+    } else {
+        node
+    }
+}
+
+fn rotate_left<T>(mut node: Box<IntervalNode<T>>) -> Box<IntervalNode<T>> {
+    let mut right = node.right.take().unwrap();
+    node.right = right.left.take();
+    node.update();
+    right.left = Some(node);
+    right.update();
+    right
+}
+
+fn rotate_right<T>(mut node: Box<IntervalNode<T>>) -> Box<IntervalNode<T>> {
+    let mut left = node.left.take().unwrap();
+    node.left = left.right.take();
+    node.update();
+    left.right = Some(node);
+    left.update();
+    left
+}
+
+fn search_nodes<'a, T>(
+    node: &'a Option<Box<IntervalNode<T>>>,
+    range: &ops::Range<usize>,
+    relation: IntervalRelation,
+    results: &mut Vec<(&'a ops::Range<usize>, &'a T)>,
+) {
+    let node = match node {
+        Some(node) => node,
+        None => return,
+    };
+    // Left subtrees only contain spans starting before the node, and right subtrees spans
+    // starting after it, which allows to prune the search
+    let (may_match, visit_left, visit_right) = match relation {
+        IntervalRelation::Overlapping => (
+            node.max_end > range.start,
+            true,
+            node.range.start < range.end,
+        ),
+        IntervalRelation::Containing => (
+            node.max_end >= range.end,
+            true,
+            node.range.start <= range.start,
+        ),
+        IntervalRelation::ContainedIn => (
+            node.max_end >= range.start,
+            node.range.start >= range.start,
+            node.range.start <= range.end,
+        ),
+    };
+    if !may_match {
+        return;
+    }
+    if visit_left {
+        search_nodes(&node.left, range, relation, results);
+    }
+    let is_match = match relation {
+        IntervalRelation::Overlapping => ranges_overlap(&node.range, range),
+        IntervalRelation::Containing => contains(&node.range, range),
+        IntervalRelation::ContainedIn => contains(range, &node.range),
+    };
+    if is_match {
+        results.push((&node.range, &node.value));
+    }
+    if visit_right {
+        search_nodes(&node.right, range, relation, results);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -22,4 +522,163 @@ mod tests {
             assert_eq!(ranges_overlap(&r2, &r1), expected_result);
         }
     }
+
+    #[test]
+    fn contains_works() {
+        assert!(contains(&(3..6), &(3..6)));
+        assert!(contains(&(3..6), &(4..4)));
+        assert!(!contains(&(3..6), &(2..4)));
+    }
+
+    #[test]
+    fn intersection_and_union_work() {
+        assert_eq!(Some(4..5), intersection(&(3..6), &(4..5)));
+        assert_eq!(None, intersection(&(3..6), &(7..9)));
+        assert_eq!(Some(3..6), union(&(3..6), &(4..5)));
+        assert_eq!(Some(1..6), union(&(3..6), &(1..3)));
+    }
+
+    #[test]
+    fn merge_overlapping_works() {
+        assert_eq!(
+            vec![1..6, 6..7],
+            merge_overlapping(&[6..7, 3..6, 1..4, 2..2])
+        );
+        assert!(merge_overlapping(&[]).is_empty());
+    }
+
+    #[test]
+    fn subtract_works() {
+        assert_eq!(vec![5..8], subtract(&(3..8), &(0..5)));
+        assert_eq!(vec![3..8], subtract(&(3..8), &(8..10)));
+        assert!(subtract(&(3..3), &(8..10)).is_empty());
+    }
+
+    #[test]
+    fn distance_works() {
+        assert_eq!(3, distance(&(8..9), &(2..5)));
+        assert_eq!(0, distance(&(2..5), &(4..9)));
+    }
+
+    fn scored(range: ops::Range<usize>, score: f64) -> ScoredRange {
+        ScoredRange { range, score }
+    }
+
+    #[test]
+    fn resolve_overlaps_should_prefer_leftmost_or_longest_spans() {
+        // Given
+        let candidates = vec![scored(2..10, 0.5), scored(0..4, 0.5), scored(10..12, 0.1)];
+
+        // When
+        let leftmost_longest = resolve_overlaps(&candidates, OverlapStrategy::LeftmostLongest);
+        let longest_first = resolve_overlaps(&candidates, OverlapStrategy::LongestFirst);
+
+        // Then
+        assert_eq!(vec![1, 2], leftmost_longest);
+        assert_eq!(vec![0, 2], longest_first);
+    }
+
+    #[test]
+    fn resolve_overlaps_should_maximize_total_score_when_optimal() {
+        // Given
+        let candidates = vec![
+            scored(0..10, 1.0),
+            scored(5..10, 0.6),
+            scored(0..5, 0.6),
+            scored(4..6, 0.3),
+        ];
+
+        // When
+        let highest_score = resolve_overlaps(&candidates, OverlapStrategy::HighestScore);
+        let optimal = resolve_overlaps(&candidates, OverlapStrategy::OptimalWeighted);
+
+        // Then
+        assert_eq!(vec![0], highest_score);
+        assert_eq!(vec![2, 1], optimal);
+    }
+
+    #[test]
+    fn resolve_overlaps_should_keep_non_overlapping_candidates() {
+        // Given
+        let candidates = vec![scored(6..8, 0.2), scored(0..3, 0.1), scored(3..6, 0.4)];
+
+        // Then
+        for strategy in &[
+            OverlapStrategy::LongestFirst,
+            OverlapStrategy::LeftmostLongest,
+            OverlapStrategy::HighestScore,
+            OverlapStrategy::OptimalWeighted,
+        ] {
+            assert_eq!(vec![1, 2, 0], resolve_overlaps(&candidates, *strategy));
+            assert!(resolve_overlaps(&[], *strategy).is_empty());
+        }
+    }
+
+    #[test]
+    fn interval_tree_should_match_linear_scans() {
+        // Given
+        let spans: Vec<ops::Range<usize>> = (0..200)
+            .map(|i| {
+                let start = (i * 37) % 101;
+                start..start + (i * 13) % 11
+            })
+            .collect();
+        let tree: IntervalTree<usize> = spans.iter().cloned().zip(0..).collect();
+        let queries = vec![0..0, 3..3, 5..20, 40..41, 0..120, 99..110, 150..160];
+
+        // Then
+        assert_eq!(200, tree.len());
+        assert!(tree.root.as_ref().unwrap().height <= 10);
+        for query in queries {
+            let search = |results: Vec<(&ops::Range<usize>, &usize)>| {
+                let mut indexes: Vec<usize> = results.into_iter().map(|(_, i)| *i).collect();
+                indexes.sort();
+                indexes
+            };
+            let scan = |predicate: &dyn Fn(&ops::Range<usize>) -> bool| {
+                (0..spans.len())
+                    .filter(|i| predicate(&spans[*i]))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                scan(&|span| ranges_overlap(span, &query)),
+                search(tree.overlapping(&query))
+            );
+            assert_eq!(
+                scan(&|span| contains(span, &query)),
+                search(tree.containing(&query))
+            );
+            assert_eq!(
+                scan(&|span| contains(&query, span)),
+                search(tree.contained_in(&query))
+            );
+        }
+    }
+
+    #[test]
+    fn interval_tree_should_return_sorted_spans() {
+        // Given
+        let mut tree = IntervalTree::new();
+        tree.insert(5..9, 'b');
+        tree.insert(0..4, 'a');
+        tree.insert(5..9, 'c');
+        tree.insert(2..7, 'd');
+
+        // When
+        let overlapping = tree.overlapping(&(3..6));
+
+        // Then
+        assert_eq!(
+            vec![
+                (&(0..4), &'a'),
+                (&(2..7), &'d'),
+                (&(5..9), &'b'),
+                (&(5..9), &'c')
+            ],
+            overlapping
+        );
+        assert!(IntervalTree::<char>::default()
+            .overlapping(&(0..10))
+            .is_empty());
+    }
 }
@@ -1,24 +1,32 @@
-use fnv::FnvHasher;
+use fnv::{FnvHashMap, FnvHasher};
+use std::cmp::{max, min};
 use std::hash::Hasher;
 use std::ops::Range;
-use unicode_normalization::char::{compose, decompose_canonical, is_combining_mark};
+use caseless::Caseless;
+use unicode_normalization::char::{
+    compose, decompose_canonical, decompose_compatible, is_combining_mark,
+};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::alignment::Alignment;
+use crate::japanese::is_half_width_sound_mark;
+use crate::language::Language;
+use crate::offset::{check_range, OffsetError, OffsetIndex};
 
 const FNV_DEFAULT_KEY: u64 = 0xcbf2_9ce4_8422_2325;
 
+/// Convert a byte range into a char range, with a single `OffsetIndex` for both ends
 pub fn convert_to_char_range(string: &str, range: &Range<usize>) -> Range<usize> {
-    Range {
-        start: convert_to_char_index(string, range.start),
-        end: convert_to_char_index(string, range.end),
-    }
+    OffsetIndex::new(string).byte_range_to_char_range(range)
 }
 
+/// Convert a char range into a byte range, with a single `OffsetIndex` for both ends
 pub fn convert_to_byte_range(string: &str, range: &Range<usize>) -> Range<usize> {
-    Range {
-        start: convert_to_byte_index(string, range.start),
-        end: convert_to_byte_index(string, range.end),
-    }
+    OffsetIndex::new(string).char_range_to_byte_range(range)
 }
 
+/// Convert a byte index into a char index by scanning the string, which is cheaper than building
+/// an `OffsetIndex` for a single conversion
 pub fn convert_to_char_index(string: &str, byte_index: usize) -> usize {
     if string.is_empty() {
         return 0;
@@ -35,6 +43,8 @@ pub fn convert_to_char_index(string: &str, byte_index: usize) -> usize {
     last_char_index + 1
 }
 
+/// Convert a char index into a byte index by scanning the string, which is cheaper than building
+/// an `OffsetIndex` for a single conversion
 pub fn convert_to_byte_index(string: &str, char_index: usize) -> usize {
     let mut result = 0;
     for (current_char_index, char) in string.chars().enumerate() {
@@ -46,11 +56,72 @@ pub fn convert_to_byte_index(string: &str, char_index: usize) -> usize {
     result
 }
 
+/// Strict version of `convert_to_char_range`, which fails on inverted ranges and on indexes
+/// which are out of bounds or not on a char boundary
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::offset::OffsetError;
+/// use snips_nlu_utils::string::try_convert_to_char_range;
+///
+/// assert_eq!(Ok(1..2), try_convert_to_char_range("aéb", &(1..3)));
+/// assert_eq!(
+///     Err(OffsetError::NotOnCharBoundary { index: 2 }),
+///     try_convert_to_char_range("aéb", &(1..2))
+/// );
+/// ```
+pub fn try_convert_to_char_range(
+    string: &str,
+    range: &Range<usize>,
+) -> Result<Range<usize>, OffsetError> {
+    check_range(range)?;
+    Ok(Range {
+        start: try_convert_to_char_index(string, range.start)?,
+        end: try_convert_to_char_index(string, range.end)?,
+    })
+}
+
+/// Strict version of `convert_to_byte_range`, which fails on inverted ranges and on indexes
+/// which are out of bounds
+pub fn try_convert_to_byte_range(
+    string: &str,
+    range: &Range<usize>,
+) -> Result<Range<usize>, OffsetError> {
+    check_range(range)?;
+    Ok(Range {
+        start: try_convert_to_byte_index(string, range.start)?,
+        end: try_convert_to_byte_index(string, range.end)?,
+    })
+}
+
+/// Strict version of `convert_to_char_index`, which fails on indexes which are out of bounds or
+/// not on a char boundary
+pub fn try_convert_to_char_index(string: &str, byte_index: usize) -> Result<usize, OffsetError> {
+    if byte_index > string.len() {
+        return Err(OffsetError::OutOfBounds {
+            index: byte_index,
+            length: string.len(),
+        });
+    }
+    if !string.is_char_boundary(byte_index) {
+        return Err(OffsetError::NotOnCharBoundary { index: byte_index });
+    }
+    Ok(string[..byte_index].chars().count())
+}
+
+/// Strict version of `convert_to_byte_index`, which fails on indexes which are out of bounds
+pub fn try_convert_to_byte_index(string: &str, char_index: usize) -> Result<usize, OffsetError> {
+    char_boundary(string, char_index)
+}
+
+/// Substring of the chars within the range, see `slice_by_char_range` for a borrowing version
+/// which fails on invalid ranges
 pub fn substring_with_char_range(string: String, range: &Range<usize>) -> String {
     string
         .chars()
         .skip(range.start)
-        .take(range.end - range.start)
+        .take(range.end.saturating_sub(range.start))
         .collect()
 }
 
@@ -59,8 +130,63 @@ pub fn prefix_until_char_index(string: String, index: usize) -> String {
 }
 
 pub fn suffix_from_char_index(string: String, index: usize) -> String {
-    let length = string.len();
-    substring_with_char_range(string, &(index..length))
+    string.chars().skip(index).collect()
+}
+
+/// Slice of the string within the char range, which fails when the range is inverted or goes
+/// past the end of the string
+///
+/// The slice is borrowed from the input and no index is allocated, which makes this function
+/// suitable for hot loops.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::offset::OffsetError;
+/// use snips_nlu_utils::string::slice_by_char_range;
+///
+/// assert_eq!(Ok("llö"), slice_by_char_range("Hellö !!", &(2..5)));
+/// assert_eq!(
+///     Err(OffsetError::OutOfBounds { index: 10, length: 8 }),
+///     slice_by_char_range("Hellö !!", &(2..10))
+/// );
+/// ```
+pub fn slice_by_char_range<'a>(
+    string: &'a str,
+    range: &Range<usize>,
+) -> Result<&'a str, OffsetError> {
+    check_range(range)?;
+    let start = char_boundary(string, range.start)?;
+    let end = char_boundary(&string[start..], range.end - range.start)
+        .map(|length| start + length)
+        .map_err(|_| OffsetError::OutOfBounds {
+            index: range.end,
+            length: string.chars().count(),
+        })?;
+    Ok(&string[start..end])
+}
+
+/// Slice of the string before the char index, which fails when the index is past the end
+pub fn prefix_by_char_index(string: &str, index: usize) -> Result<&str, OffsetError> {
+    char_boundary(string, index).map(|end| &string[..end])
+}
+
+/// Slice of the string from the char index, which fails when the index is past the end
+pub fn suffix_by_char_index(string: &str, index: usize) -> Result<&str, OffsetError> {
+    char_boundary(string, index).map(|start| &string[start..])
+}
+
+/// Byte index of the char boundary at the char index, the end of the string being a boundary
+fn char_boundary(string: &str, char_index: usize) -> Result<usize, OffsetError> {
+    string
+        .char_indices()
+        .map(|(byte_index, _)| byte_index)
+        .chain(::std::iter::once(string.len()))
+        .nth(char_index)
+        .ok_or_else(|| OffsetError::OutOfBounds {
+            index: char_index,
+            length: string.chars().count(),
+        })
 }
 
 /// Apply the following normalization successively:
@@ -97,6 +223,304 @@ pub fn remove_diacritics(string: &str) -> String {
         .collect()
 }
 
+/// Fold the string to ASCII, in the manner of the Lucene ASCII folding filter
+///
+/// Unlike `remove_diacritics`, this also handles letters which have no canonical decomposition,
+/// such as "ø" or "ł", expands ligatures such as "æ" into several letters, and folds
+/// typographic punctuation and compatibility variants such as full-width letters. Characters
+/// which have no ASCII equivalent are kept unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::string::ascii_fold;
+///
+/// assert_eq!("Royksopp", ascii_fold("Røyksopp"));
+/// assert_eq!("Lodz", ascii_fold("Łódź"));
+/// assert_eq!("aether \"oeuvre\"", ascii_fold("æther “œuvre”"));
+/// ```
+pub fn ascii_fold(string: &str) -> String {
+    let mut result = String::with_capacity(string.len());
+    for c in string.chars() {
+        if c.is_ascii() {
+            result.push(c);
+        } else if let Some(folded) = ascii_folding(c) {
+            result.push_str(folded);
+        } else if !is_combining_mark(c) {
+            let mut decomposition = String::new();
+            decompose_compatible(c, |d| match ascii_folding(d) {
+                Some(folded) => decomposition.push_str(folded),
+                None if !is_combining_mark(d) => decomposition.push(d),
+                None => {}
+            });
+            if decomposition.is_ascii() {
+                result.push_str(&decomposition);
+            } else {
+                result.push(c);
+            }
+        }
+    }
+    result
+}
+
+/// ASCII equivalents of the characters which cannot be folded through their compatibility
+/// decomposition
+fn ascii_folding(character: char) -> Option<&'static str> {
+    let folded = match character {
+        'Æ' | 'Ǣ' | 'Ǽ' => "AE",
+        'æ' | 'ǣ' | 'ǽ' => "ae",
+        'Ð' | 'Đ' | 'Ɖ' | 'Ɗ' => "D",
+        'ð' | 'đ' | 'ɖ' | 'ɗ' | 'ȡ' => "d",
+        'Ħ' => "H",
+        'ħ' | 'ɦ' => "h",
+        'ı' | 'ɨ' => "i",
+        'Ɨ' => "I",
+        'ȷ' | 'ɉ' => "j",
+        'Ɉ' => "J",
+        'ĸ' => "q",
+        'Ł' | 'Ƚ' => "L",
+        'ł' | 'ƚ' | 'ȴ' | 'ɫ' | 'ɬ' | 'ɭ' => "l",
+        'Ŋ' | 'Ɲ' => "N",
+        'ŋ' | 'ɲ' | 'ɳ' | 'ȵ' => "n",
+        'Ø' | 'Ǿ' | 'Ɵ' => "O",
+        'ø' | 'ǿ' | 'ɵ' => "o",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'ß' => "ss",
+        'ẞ' => "SS",
+        'Þ' => "TH",
+        'þ' => "th",
+        'Ŧ' | 'Ƭ' | 'Ʈ' | 'Ⱦ' => "T",
+        'ŧ' | 'ƭ' | 'ʈ' | 'ȶ' => "t",
+        'Ƀ' | 'Ɓ' => "B",
+        'ƀ' | 'ɓ' => "b",
+        'Ƈ' | 'Ȼ' => "C",
+        'ƈ' | 'ȼ' | 'ɕ' => "c",
+        'Ɇ' => "E",
+        'ɇ' => "e",
+        'Ƒ' => "F",
+        'ƒ' => "f",
+        'ɠ' => "g",
+        'Ƙ' => "K",
+        'ƙ' => "k",
+        'ɱ' => "m",
+        'Ƥ' => "P",
+        'ƥ' => "p",
+        'Ɍ' => "R",
+        'ɍ' | 'ɼ' | 'ɽ' | 'ɾ' => "r",
+        'ȿ' | 'ʂ' => "s",
+        'Ʉ' => "U",
+        'Ʋ' => "V",
+        'Ɏ' | 'Ƴ' => "Y",
+        'ɏ' | 'ƴ' => "y",
+        'Ƶ' => "Z",
+        'ƶ' | 'ɀ' | 'ʐ' | 'ʑ' => "z",
+        '‘' | '’' | '‚' | '‛' | '‹' | '›' | '′' => "'",
+        '“' | '”' | '„' | '‟' | '«' | '»' | '″' => "\"",
+        '‐' | '‑' | '‒' | '–' | '—' | '―' | '−' => "-",
+        '⁄' => "/",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+/// Fold german umlauts and eszett into their transcription, e.g. "ä" becomes "ae" and "ß"
+/// becomes "ss"
+///
+/// Capital umlauts are transcribed in uppercase when they are part of an uppercase word.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::string::german_transcription;
+///
+/// assert_eq!("Mueller Strasse", german_transcription("Müller Straße"));
+/// assert_eq!("Aerger AERGER", german_transcription("Ärger ÄRGER"));
+/// ```
+pub fn german_transcription(string: &str) -> String {
+    let chars: Vec<char> = string.nfc().collect();
+    let mut result = String::with_capacity(string.len());
+    for (index, c) in chars.iter().enumerate() {
+        let (base, suffix) = match c {
+            'ä' => ('a', 'e'),
+            'ö' => ('o', 'e'),
+            'ü' => ('u', 'e'),
+            'ß' => ('s', 's'),
+            'Ä' => ('A', 'e'),
+            'Ö' => ('O', 'e'),
+            'Ü' => ('U', 'e'),
+            'ẞ' => ('S', 's'),
+            _ => {
+                result.push(*c);
+                continue;
+            }
+        };
+        result.push(base);
+        let next = chars.get(index + 1).cloned().unwrap_or(' ');
+        let previous_is_uppercase = index > 0 && chars[index - 1].is_uppercase();
+        let is_uppercase_word =
+            next.is_uppercase() || (previous_is_uppercase && !next.is_lowercase());
+        if base.is_uppercase() && is_uppercase_word {
+            result.extend(suffix.to_uppercase());
+        } else {
+            result.push(suffix);
+        }
+    }
+    result
+}
+
+/// Return the normalized spellings of a german string, with umlauts both transcribed and with
+/// their diacritics removed
+///
+/// This is useful to build gazetteer keys which match both spellings.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::string::german_variants;
+///
+/// assert_eq!(vec!["mueller", "muller"], german_variants("Müller"));
+/// assert_eq!(vec!["berlin"], german_variants("Berlin"));
+/// ```
+pub fn german_variants(string: &str) -> Vec<String> {
+    let mut variants = vec![
+        normalize(&german_transcription(string)),
+        normalize(&string.replace('ß', "ss").replace('ẞ', "SS")),
+    ];
+    variants.sort();
+    variants.dedup();
+    variants
+}
+
+/// Apply the Unicode full case folding, as defined by the C and F mappings of CaseFolding.txt
+///
+/// Case folding is meant for caseless matching: unlike lowercasing, it maps strings such as
+/// "STRASSE" and "Straße" to the same value.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::string::case_fold;
+///
+/// assert_eq!("strasse", case_fold("Straße"));
+/// assert_eq!("strasse", case_fold("STRASSE"));
+/// assert_eq!("σοφοσ", case_fold("ΣΟΦΟΣ"));
+/// ```
+pub fn case_fold(string: &str) -> String {
+    string.chars().default_case_fold().collect()
+}
+
+/// Same as `case_fold`, but also apply the language specific mappings, such as the turkish
+/// dotted and dotless i
+pub fn case_fold_with_language(string: &str, language: Language) -> String {
+    match language {
+        Language::TR => case_fold(&turkic_lowercase_i(string)),
+        _ => case_fold(string),
+    }
+}
+
+/// Lowercase the string according to the rules of the language
+///
+/// The greek final sigma is handled in all languages, while the turkish dotted and dotless i
+/// are only handled in turkish.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::language::Language;
+/// use snips_nlu_utils::string::lowercase_with_language;
+///
+/// assert_eq!("ıspanak istanbul", lowercase_with_language("ISPANAK İSTANBUL", Language::TR));
+/// assert_eq!("ispanak", lowercase_with_language("ISPANAK", Language::EN));
+/// assert_eq!("σοφος", lowercase_with_language("ΣΟΦΟΣ", Language::EN));
+/// ```
+pub fn lowercase_with_language(string: &str, language: Language) -> String {
+    match language {
+        Language::TR => turkic_lowercase_i(string).to_lowercase(),
+        _ => string.to_lowercase(),
+    }
+}
+
+/// Lowercase the turkish capital i, with and without dot, which must be done before the default
+/// lowercasing or case folding
+pub(crate) fn turkic_lowercase_i(string: &str) -> String {
+    string
+        .replace("I\u{307}", "i")
+        .replace('I', "ı")
+        .replace('İ', "i")
+}
+
+/// Same as `normalize`, but also return the alignment between the input and the normalized
+/// string
+///
+/// Trimmed whitespaces are deleted in the alignment, and combining marks are aligned together
+/// with the char they apply to.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::string::normalize_with_alignment;
+///
+/// let (normalized, alignment) = normalize_with_alignment(" Cafe\u{301} Noir");
+/// assert_eq!("cafe noir", normalized);
+/// assert_eq!(1..6, alignment.original_char_range(&(0..4)));
+/// assert_eq!(5..9, alignment.normalized_byte_range(&(8..12)));
+/// ```
+pub fn normalize_with_alignment(string: &str) -> (String, Alignment) {
+    let trimmed = string.trim();
+    let trimmed_start = trimmed.as_ptr() as usize - string.as_ptr() as usize;
+    let trimmed_end = trimmed_start + trimmed.len();
+    // Lowercasing is done on the whole string, as it depends on the context in some cases, such
+    // as the greek final sigma, and the lowercased string is then sliced sequence by sequence
+    let normalized = remove_diacritics(trimmed).to_lowercase();
+    let mut alignment = Alignment::default();
+    alignment.push(&string[..trimmed_start], "");
+    let mut normalized_index = 0;
+    for original in split_combining_sequences(trimmed) {
+        let normalized_length: usize = remove_diacritics(original)
+            .chars()
+            .flat_map(char::to_lowercase)
+            .map(char::len_utf8)
+            .sum();
+        let normalized_end = normalized_index + normalized_length;
+        alignment.push(original, &normalized[normalized_index..normalized_end]);
+        normalized_index = normalized_end;
+    }
+    alignment.push(&string[trimmed_end..], "");
+    (normalized, alignment)
+}
+
+/// Same as `remove_diacritics`, but also return the alignment between the input and the
+/// output string
+///
+/// Combining marks are aligned together with the char they apply to.
+pub fn remove_diacritics_with_alignment(string: &str) -> (String, Alignment) {
+    let mut result = String::with_capacity(string.len());
+    let mut alignment = Alignment::default();
+    for original in split_combining_sequences(string) {
+        let cleaned = remove_diacritics(original);
+        alignment.push(original, &cleaned);
+        result.push_str(&cleaned);
+    }
+    (result, alignment)
+}
+
+/// Split the string into sequences made of a char followed by its combining marks
+///
+/// Half-width japanese sound marks are also kept with the katakana they apply to, so that they
+/// can be composed with it.
+pub(crate) fn split_combining_sequences(string: &str) -> impl Iterator<Item = &str> {
+    let mut starts: Vec<usize> = string
+        .char_indices()
+        .filter(|(index, c)| {
+            *index == 0 || !(is_combining_mark(*c) || is_half_width_sound_mark(*c))
+        })
+        .map(|(index, _)| index)
+        .collect();
+    starts.push(string.len());
+    (0..starts.len() - 1).map(move |i| &string[starts[i]..starts[i + 1]])
+}
+
 fn remove_combination_marks(character: char) -> Option<char> {
     let mut decomposition = Vec::<char>::new();
     decompose_canonical(character, |c| {
@@ -142,6 +566,141 @@ pub fn get_shape(string: &str) -> &'static str {
     }
 }
 
+/// Coarse shape of a word
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Empty,
+    Lowercase,
+    Uppercase,
+    TitleCase,
+    MixedCase,
+    Digit,
+    Punctuation,
+    /// Mix of letters and digits, e.g. "B52" or "3pm"
+    Alphanumeric,
+    /// Letters without case, e.g. "東京"
+    Other,
+}
+
+impl Shape {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Shape::Empty => "",
+            Shape::Lowercase => "xxx",
+            Shape::Uppercase => "XXX",
+            Shape::TitleCase => "Xxx",
+            Shape::MixedCase => "xX",
+            Shape::Digit => "ddd",
+            Shape::Punctuation => "...",
+            Shape::Alphanumeric => "xd",
+            Shape::Other => "o",
+        }
+    }
+}
+
+/// Get the `Shape` of a word
+///
+/// Unlike `get_shape`, digits, punctuation, alphanumeric and empty strings have their own
+/// shape. Chars which are neither alphanumeric nor whitespace are ignored when determining the
+/// case, so that "don't" is lowercase.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::string::{word_shape, Shape};
+///
+/// assert_eq!(Shape::TitleCase, word_shape("Hello"));
+/// assert_eq!(Shape::Alphanumeric, word_shape("iPhone11"));
+/// assert_eq!(Shape::Digit, word_shape("2019"));
+/// assert_eq!(Shape::Punctuation, word_shape("?!"));
+/// assert_eq!(Shape::Empty, word_shape(""));
+/// ```
+pub fn word_shape(string: &str) -> Shape {
+    if string.is_empty() {
+        return Shape::Empty;
+    }
+    let has_letters = string.chars().any(char::is_alphabetic);
+    let has_digits = string.chars().any(char::is_numeric);
+    match (has_letters, has_digits) {
+        (true, true) => return Shape::Alphanumeric,
+        (false, true) => return Shape::Digit,
+        (false, false) => return Shape::Punctuation,
+        (true, false) => {}
+    }
+    let cased: String = string
+        .chars()
+        .filter(|c| c.is_lowercase() || c.is_uppercase())
+        .collect();
+    if cased.is_empty() {
+        Shape::Other
+    } else if cased.chars().all(char::is_lowercase) {
+        Shape::Lowercase
+    } else if cased.chars().all(char::is_uppercase) {
+        Shape::Uppercase
+    } else if is_title_case(&cased) {
+        Shape::TitleCase
+    } else {
+        Shape::MixedCase
+    }
+}
+
+/// Get the detailed shape of the string, where uppercase letters are replaced with "X", other
+/// letters with "x", digits with "d", and other chars are kept
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::string::detailed_shape;
+///
+/// assert_eq!("Xxdd", detailed_shape("Ab12"));
+/// assert_eq!("dd:dd", detailed_shape("10:30"));
+/// ```
+pub fn detailed_shape(string: &str) -> String {
+    string.chars().map(shape_char).collect()
+}
+
+/// Same as `detailed_shape`, but runs of the same shape char are truncated to
+/// `max_run_length`, as done by spaCy with a length of 4
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::string::compressed_shape;
+///
+/// assert_eq!("Xxxxx", compressed_shape("Applesauce", 4));
+/// assert_eq!("xXxxxxdd", compressed_shape("iPhone11", 4));
+/// assert_eq!("Xxd", compressed_shape("Route66", 1));
+/// ```
+pub fn compressed_shape(string: &str, max_run_length: usize) -> String {
+    let mut result = String::with_capacity(string.len());
+    let mut previous = None;
+    let mut run_length = 0;
+    for c in string.chars().map(shape_char) {
+        if previous == Some(c) {
+            run_length += 1;
+        } else {
+            previous = Some(c);
+            run_length = 1;
+        }
+        if run_length <= max_run_length {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn shape_char(c: char) -> char {
+    if c.is_uppercase() {
+        'X'
+    } else if c.is_alphabetic() {
+        'x'
+    } else if c.is_numeric() {
+        'd'
+    } else {
+        c
+    }
+}
+
 fn is_title_case(string: &str) -> bool {
     let mut first = true;
     for c in string.chars() {
@@ -162,6 +721,229 @@ pub fn hash_str_to_i32(input: &str) -> i32 {
     hasher.finish() as i32
 }
 
+/// Compute the Levenshtein distance between two strings, in number of Unicode scalar values
+///
+/// When `normalize_input` is true, both strings are normalized with `normalize` beforehand.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::string::levenshtein_distance;
+///
+/// assert_eq!(1, levenshtein_distance("spottify", "spotify", false));
+/// assert_eq!(0, levenshtein_distance("Beyoncé", "beyonce", true));
+/// ```
+pub fn levenshtein_distance(lhs: &str, rhs: &str, normalize_input: bool) -> usize {
+    let (lhs, rhs) = chars_to_compare(lhs, rhs, normalize_input);
+    bounded_levenshtein(&lhs, &rhs, usize::MAX).unwrap()
+}
+
+/// Same as `levenshtein_distance`, but stop as soon as the distance is known to be greater than
+/// `max_distance`, in which case `None` is returned
+pub fn levenshtein_distance_bounded(
+    lhs: &str,
+    rhs: &str,
+    max_distance: usize,
+    normalize_input: bool,
+) -> Option<usize> {
+    let (lhs, rhs) = chars_to_compare(lhs, rhs, normalize_input);
+    bounded_levenshtein(&lhs, &rhs, max_distance)
+}
+
+/// Compute the Damerau-Levenshtein distance between two strings, in number of Unicode scalar
+/// values
+///
+/// Contrary to the Levenshtein distance, transpositions of adjacent characters count as a single
+/// edit. This is the unrestricted version of the distance, so substrings can be edited more than
+/// once.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::string::damerau_levenshtein_distance;
+///
+/// assert_eq!(1, damerau_levenshtein_distance("spotfiy", "spotify", false));
+/// assert_eq!(2, damerau_levenshtein_distance("ca", "abc", false));
+/// ```
+pub fn damerau_levenshtein_distance(lhs: &str, rhs: &str, normalize_input: bool) -> usize {
+    let (lhs, rhs) = chars_to_compare(lhs, rhs, normalize_input);
+    bounded_damerau_levenshtein(&lhs, &rhs, usize::MAX).unwrap()
+}
+
+/// Same as `damerau_levenshtein_distance`, but stop as soon as the distance is known to be
+/// greater than `max_distance`, in which case `None` is returned
+pub fn damerau_levenshtein_distance_bounded(
+    lhs: &str,
+    rhs: &str,
+    max_distance: usize,
+    normalize_input: bool,
+) -> Option<usize> {
+    let (lhs, rhs) = chars_to_compare(lhs, rhs, normalize_input);
+    bounded_damerau_levenshtein(&lhs, &rhs, max_distance)
+}
+
+/// Compute a similarity score between 0 and 1, based on the Levenshtein distance normalized by
+/// the length of the longest string
+///
+/// Two empty strings have a similarity of 1.
+pub fn levenshtein_similarity(lhs: &str, rhs: &str, normalize_input: bool) -> f64 {
+    let (lhs, rhs) = chars_to_compare(lhs, rhs, normalize_input);
+    let max_length = max(lhs.len(), rhs.len());
+    if max_length == 0 {
+        return 1.0;
+    }
+    let distance = bounded_levenshtein(&lhs, &rhs, usize::MAX).unwrap();
+    1.0 - distance as f64 / max_length as f64
+}
+
+/// Compute the Jaro-Winkler similarity between two strings, which is between 0 and 1
+///
+/// The standard prefix scale of 0.1 is used, with a common prefix of at most 4 characters.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::string::jaro_winkler_similarity;
+///
+/// assert!((jaro_winkler_similarity("martha", "marhta", false) - 0.9611).abs() < 1e-4);
+/// assert_eq!(1.0, jaro_winkler_similarity("Spotify", "spotify", true));
+/// ```
+pub fn jaro_winkler_similarity(lhs: &str, rhs: &str, normalize_input: bool) -> f64 {
+    let (lhs, rhs) = chars_to_compare(lhs, rhs, normalize_input);
+    let jaro = jaro_similarity(&lhs, &rhs);
+    let prefix_length = lhs
+        .iter()
+        .zip(rhs.iter())
+        .take(4)
+        .take_while(|(l, r)| l == r)
+        .count();
+    jaro + prefix_length as f64 * 0.1 * (1.0 - jaro)
+}
+
+/// Compute the Jaro-Winkler distance, which is `1 - jaro_winkler_similarity`
+pub fn jaro_winkler_distance(lhs: &str, rhs: &str, normalize_input: bool) -> f64 {
+    1.0 - jaro_winkler_similarity(lhs, rhs, normalize_input)
+}
+
+fn chars_to_compare(lhs: &str, rhs: &str, normalize_input: bool) -> (Vec<char>, Vec<char>) {
+    if normalize_input {
+        (normalize(lhs).chars().collect(), normalize(rhs).chars().collect())
+    } else {
+        (lhs.chars().collect(), rhs.chars().collect())
+    }
+}
+
+fn bounded_levenshtein(lhs: &[char], rhs: &[char], max_distance: usize) -> Option<usize> {
+    if max(lhs.len(), rhs.len()) - min(lhs.len(), rhs.len()) > max_distance {
+        return None;
+    }
+    let mut previous_row: Vec<usize> = (0..=rhs.len()).collect();
+    let mut current_row = vec![0; rhs.len() + 1];
+    for (i, lhs_char) in lhs.iter().enumerate() {
+        current_row[0] = i + 1;
+        let mut row_min = current_row[0];
+        for (j, rhs_char) in rhs.iter().enumerate() {
+            let substitution_cost = if lhs_char == rhs_char { 0 } else { 1 };
+            current_row[j + 1] = min(
+                previous_row[j] + substitution_cost,
+                min(previous_row[j + 1], current_row[j]) + 1,
+            );
+            row_min = min(row_min, current_row[j + 1]);
+        }
+        if row_min > max_distance {
+            return None;
+        }
+        ::std::mem::swap(&mut previous_row, &mut current_row);
+    }
+    Some(previous_row[rhs.len()]).filter(|d| *d <= max_distance)
+}
+
+fn bounded_damerau_levenshtein(lhs: &[char], rhs: &[char], max_distance: usize) -> Option<usize> {
+    if max(lhs.len(), rhs.len()) - min(lhs.len(), rhs.len()) > max_distance {
+        return None;
+    }
+    // The matrix has an additional first row and column, filled with the maximum distance, to
+    // handle transpositions at the start of the strings
+    let width = rhs.len() + 2;
+    let infinity = lhs.len() + rhs.len();
+    let mut matrix = vec![infinity; (lhs.len() + 2) * width];
+    for i in 0..=lhs.len() {
+        matrix[(i + 1) * width + 1] = i;
+    }
+    for j in 0..=rhs.len() {
+        matrix[width + j + 1] = j;
+    }
+    let mut last_row_of_char: FnvHashMap<char, usize> = FnvHashMap::default();
+    for i in 1..=lhs.len() {
+        let mut last_matching_column = 0;
+        let mut row_min = i;
+        for j in 1..=rhs.len() {
+            let last_matching_row = last_row_of_char.get(&rhs[j - 1]).cloned().unwrap_or(0);
+            let previous_matching_column = last_matching_column;
+            let substitution_cost = if lhs[i - 1] == rhs[j - 1] {
+                last_matching_column = j;
+                0
+            } else {
+                1
+            };
+            let distance = min(
+                matrix[i * width + j] + substitution_cost,
+                min(matrix[(i + 1) * width + j], matrix[i * width + j + 1]) + 1,
+            );
+            let transposition = matrix[last_matching_row * width + previous_matching_column]
+                + (i - last_matching_row - 1)
+                + 1
+                + (j - previous_matching_column - 1);
+            let distance = min(distance, transposition);
+            matrix[(i + 1) * width + j + 1] = distance;
+            row_min = min(row_min, distance);
+        }
+        // Cells of the following rows, including those reached through transpositions, can't
+        // be lower than the minimum of the current row
+        if row_min > max_distance {
+            return None;
+        }
+        last_row_of_char.insert(lhs[i - 1], i);
+    }
+    Some(matrix[(lhs.len() + 1) * width + rhs.len() + 1]).filter(|d| *d <= max_distance)
+}
+
+fn jaro_similarity(lhs: &[char], rhs: &[char]) -> f64 {
+    if lhs.is_empty() && rhs.is_empty() {
+        return 1.0;
+    }
+    if lhs.is_empty() || rhs.is_empty() {
+        return 0.0;
+    }
+    let match_window = (max(lhs.len(), rhs.len()) / 2).saturating_sub(1);
+    let mut lhs_matches = vec![false; lhs.len()];
+    let mut rhs_matches = vec![false; rhs.len()];
+    let mut nb_matches = 0;
+    for (i, lhs_char) in lhs.iter().enumerate() {
+        let start = i.saturating_sub(match_window);
+        let end = min(i + match_window + 1, rhs.len());
+        for j in start..end {
+            if !rhs_matches[j] && rhs[j] == *lhs_char {
+                lhs_matches[i] = true;
+                rhs_matches[j] = true;
+                nb_matches += 1;
+                break;
+            }
+        }
+    }
+    if nb_matches == 0 {
+        return 0.0;
+    }
+    let lhs_matched = lhs.iter().zip(lhs_matches).filter(|(_, m)| *m).map(|(c, _)| c);
+    let rhs_matched = rhs.iter().zip(rhs_matches).filter(|(_, m)| *m).map(|(c, _)| c);
+    let nb_transpositions = lhs_matched.zip(rhs_matched).filter(|(l, r)| l != r).count() / 2;
+    let nb_matches = nb_matches as f64;
+    (nb_matches / lhs.len() as f64
+        + nb_matches / rhs.len() as f64
+        + (nb_matches - nb_transpositions as f64) / nb_matches)
+        / 3.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -203,6 +985,128 @@ mod tests {
         assert_eq!("ö !!", &suffix);
     }
 
+    #[test]
+    fn try_conversions_should_reject_invalid_indexes() {
+        // Given
+        let text = "aé😀b";
+
+        // Then
+        assert_eq!(Ok(3), try_convert_to_char_index(text, 7));
+        assert_eq!(Ok(8), try_convert_to_byte_index(text, 4));
+        assert_eq!(Ok(1..3), try_convert_to_byte_range(text, &(1..2)));
+        assert_eq!(
+            Err(OffsetError::NotOnCharBoundary { index: 2 }),
+            try_convert_to_char_index(text, 2)
+        );
+        assert_eq!(
+            Err(OffsetError::OutOfBounds {
+                index: 9,
+                length: 8
+            }),
+            try_convert_to_char_range(text, &(0..9))
+        );
+        assert_eq!(
+            Err(OffsetError::OutOfBounds {
+                index: 5,
+                length: 4
+            }),
+            try_convert_to_byte_index(text, 5)
+        );
+        assert_eq!(
+            Err(OffsetError::InvertedRange { start: 2, end: 0 }),
+            try_convert_to_byte_range(text, &Range { start: 2, end: 0 })
+        );
+    }
+
+    #[test]
+    fn suffix_should_not_overflow_on_multibyte_strings() {
+        assert_eq!("", suffix_from_char_index("日本".to_string(), 7));
+        assert_eq!(
+            "",
+            substring_with_char_range("日本".to_string(), &Range { start: 2, end: 1 })
+        );
+    }
+
+    #[test]
+    fn borrowing_slices_work() {
+        // Given
+        let text = "Hellö !!";
+
+        // Then
+        assert_eq!(Ok("Hellö"), prefix_by_char_index(text, 5));
+        assert_eq!(Ok("ö !!"), suffix_by_char_index(text, 4));
+        assert_eq!(Ok(""), suffix_by_char_index(text, 8));
+        assert_eq!(Ok(""), slice_by_char_range(text, &(8..8)));
+        assert_eq!(
+            Err(OffsetError::OutOfBounds {
+                index: 9,
+                length: 8
+            }),
+            prefix_by_char_index(text, 9)
+        );
+        assert_eq!(
+            Err(OffsetError::InvertedRange { start: 3, end: 2 }),
+            slice_by_char_range(text, &Range { start: 3, end: 2 })
+        );
+    }
+
+    #[test]
+    fn ascii_fold_works() {
+        assert_eq!("AEsir Ostergard", ascii_fold("Æsir Østergård"));
+        assert_eq!("Dordevic Hajek", ascii_fold("Đorđević Hájek"));
+        assert_eq!("fi 2 ABC - 1/2", ascii_fold("ﬁ ² ＡＢＣ – ½"));
+        assert_eq!("kosc Istanbul", ascii_fold("kos\u{301}c İstanbul"));
+        assert_eq!("東京", ascii_fold("東京"));
+    }
+
+    #[test]
+    fn german_transcription_works() {
+        assert_eq!("Groesse GROESSE", german_transcription("Größe GRÖSSE"));
+        assert_eq!("Uebel UEBEL Ue", german_transcription("Übel ÜBEL Ü"));
+        assert_eq!("Koeln", german_transcription("Ko\u{308}ln"));
+        assert_eq!("Francais", german_transcription("Francais"));
+    }
+
+    #[test]
+    fn german_variants_works() {
+        assert_eq!(vec!["strasse"], german_variants("Straße"));
+        assert_eq!(
+            vec!["duesseldorf", "dusseldorf"],
+            german_variants(" Düsseldorf ")
+        );
+    }
+
+    #[test]
+    fn word_shape_works() {
+        assert_eq!(Shape::Lowercase, word_shape("don't"));
+        assert_eq!(Shape::Uppercase, word_shape("NASA"));
+        assert_eq!(Shape::MixedCase, word_shape("McDonald"));
+        assert_eq!(Shape::Alphanumeric, word_shape("B52"));
+        assert_eq!(Shape::Alphanumeric, word_shape("3pm"));
+        assert_eq!(Shape::Digit, word_shape("１２"));
+        assert_eq!(Shape::Punctuation, word_shape("..."));
+        assert_eq!(Shape::Other, word_shape("東京"));
+        assert_eq!("xd", word_shape("3pm").as_str());
+    }
+
+    #[test]
+    fn detailed_shapes_work() {
+        assert_eq!("", detailed_shape(""));
+        assert_eq!("XxXxxx-x", detailed_shape("McCain-é"));
+        assert_eq!("dd.dd", compressed_shape("12.50", 4));
+        assert_eq!("dddd.dd", compressed_shape("123456.50", 4));
+        assert_eq!("", compressed_shape("abc", 0));
+    }
+
+    #[test]
+    fn case_fold_works() {
+        assert_eq!(case_fold("Straße"), case_fold("STRASSE"));
+        assert_eq!("fi", case_fold("ﬁ"));
+        assert_eq!("i\u{307}", case_fold("İ"));
+        assert_eq!("i", case_fold_with_language("İ", Language::TR));
+        assert_eq!("ı", case_fold_with_language("I", Language::TR));
+    }
+
     #[test]
     fn remove_combination_marks_works() {
         assert_eq!(Some('c'.to_owned()), remove_combination_marks('ç'));
@@ -229,4 +1133,88 @@ mod tests {
         assert_eq!("XXX", get_shape("HELLO"));
         assert_eq!("xX", get_shape("hEllo"));
     }
+
+    #[test]
+    fn levenshtein_distance_works() {
+        assert_eq!(0, levenshtein_distance("", "", false));
+        assert_eq!(3, levenshtein_distance("", "abc", false));
+        assert_eq!(3, levenshtein_distance("kitten", "sitting", false));
+        assert_eq!(1, levenshtein_distance("héllo", "hello", false));
+        assert_eq!(0, levenshtein_distance(" HÉLLO", "hello", true));
+        assert_eq!(2, levenshtein_distance("ab", "ba", false));
+    }
+
+    #[test]
+    fn levenshtein_distance_bounded_works() {
+        assert_eq!(Some(3), levenshtein_distance_bounded("kitten", "sitting", 3, false));
+        assert_eq!(None, levenshtein_distance_bounded("kitten", "sitting", 2, false));
+        assert_eq!(None, levenshtein_distance_bounded("a", "abcd", 2, false));
+    }
+
+    #[test]
+    fn damerau_levenshtein_distance_works() {
+        assert_eq!(0, damerau_levenshtein_distance("", "", false));
+        assert_eq!(1, damerau_levenshtein_distance("ab", "ba", false));
+        assert_eq!(2, damerau_levenshtein_distance("ca", "abc", false));
+        assert_eq!(3, damerau_levenshtein_distance("kitten", "sitting", false));
+        assert_eq!(1, damerau_levenshtein_distance("Spotfiy", "spotify", true));
+    }
+
+    #[test]
+    fn damerau_levenshtein_distance_bounded_works() {
+        assert_eq!(Some(1), damerau_levenshtein_distance_bounded("ab", "ba", 1, false));
+        assert_eq!(None, damerau_levenshtein_distance_bounded("kitten", "sitting", 2, false));
+    }
+
+    #[test]
+    fn levenshtein_similarity_works() {
+        assert_eq!(1.0, levenshtein_similarity("", "", false));
+        assert_eq!(0.5, levenshtein_similarity("ab", "ac", false));
+        assert_eq!(1.0, levenshtein_similarity("Äb", "ab", true));
+    }
+
+    #[test]
+    fn jaro_winkler_similarity_works() {
+        assert_eq!(1.0, jaro_winkler_similarity("", "", false));
+        assert_eq!(0.0, jaro_winkler_similarity("abc", "", false));
+        assert_eq!(0.0, jaro_winkler_similarity("abc", "xyz", false));
+        assert!((jaro_winkler_similarity("dixon", "dicksonx", false) - 0.8133).abs() < 1e-4);
+        assert!((jaro_winkler_similarity("dwayne", "duane", false) - 0.84).abs() < 1e-4);
+        assert!((jaro_winkler_distance("martha", "marhta", false) - 0.0389).abs() < 1e-4);
+    }
+
+    #[test]
+    fn normalize_with_alignment_works() {
+        // Given
+        let input = "  HÉllo\u{301} ΟΔΟΣ İ ";
+
+        // When
+        let (normalized, alignment) = normalize_with_alignment(input);
+
+        // Then
+        assert_eq!(normalize(input), normalized);
+        assert_eq!("hello οδο\u{3c2} i", normalized);
+        assert_eq!(2..8, alignment.original_char_range(&(0..5)));
+        assert_eq!(2..10, alignment.original_byte_range(&(0..5)));
+        assert_eq!(9..13, alignment.original_char_range(&(6..10)));
+        assert_eq!(14..15, alignment.original_char_range(&(11..12)));
+        assert_eq!(11..12, alignment.normalized_char_range(&(14..15)));
+        assert_eq!(0..1, alignment.normalized_char_range(&(0..3)));
+        assert_eq!(12..12, alignment.normalized_char_range(&(15..16)));
+    }
+
+    #[test]
+    fn remove_diacritics_with_alignment_works() {
+        // Given
+        let input = "e\u{301}çÀ";
+
+        // When
+        let (result, alignment) = remove_diacritics_with_alignment(input);
+
+        // Then
+        assert_eq!(remove_diacritics(input), result);
+        assert_eq!(0..2, alignment.original_char_range(&(0..1)));
+        assert_eq!(3..5, alignment.original_byte_range(&(1..2)));
+        assert_eq!(2..3, alignment.normalized_byte_range(&(5..7)));
+    }
 }
@@ -0,0 +1,478 @@
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+use failure::format_err;
+use serde::{Deserialize, Serialize};
+
+use crate::range::intersection;
+use crate::token::Token;
+
+/// Tagging scheme used to encode spans as a sequence of token tags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaggingScheme {
+    /// Inside / Outside
+    IO,
+    /// Begin / Inside / Outside
+    BIO,
+    /// Begin / Inside / Last / Outside / Unit
+    BILOU,
+}
+
+/// Tag of a single token, formatted as "O", "B-label", "I-label", "L-label" or "U-label"
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Tag {
+    Outside,
+    Begin(String),
+    Inside(String),
+    Last(String),
+    Unit(String),
+}
+
+impl Tag {
+    pub fn label(&self) -> Option<&str> {
+        match self {
+            Tag::Outside => None,
+            Tag::Begin(label) | Tag::Inside(label) | Tag::Last(label) | Tag::Unit(label) => {
+                Some(label)
+            }
+        }
+    }
+
+    fn is_allowed_by(&self, scheme: TaggingScheme) -> bool {
+        matches!(
+            (self, scheme),
+            (Tag::Outside, _)
+                | (Tag::Inside(_), _)
+                | (Tag::Begin(_), TaggingScheme::BIO)
+                | (_, TaggingScheme::BILOU)
+        )
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Tag::Outside => write!(f, "O"),
+            Tag::Begin(label) => write!(f, "B-{}", label),
+            Tag::Inside(label) => write!(f, "I-{}", label),
+            Tag::Last(label) => write!(f, "L-{}", label),
+            Tag::Unit(label) => write!(f, "U-{}", label),
+        }
+    }
+}
+
+impl FromStr for Tag {
+    type Err = failure::Error;
+    fn from_str(it: &str) -> Result<Tag, Self::Err> {
+        if it == "O" {
+            return Ok(Tag::Outside);
+        }
+        let label = it.get(2..).filter(|label| !label.is_empty());
+        match (it.get(..2), label) {
+            (Some("B-"), Some(label)) => Ok(Tag::Begin(label.to_string())),
+            (Some("I-"), Some(label)) => Ok(Tag::Inside(label.to_string())),
+            (Some("L-"), Some(label)) => Ok(Tag::Last(label.to_string())),
+            (Some("U-"), Some(label)) => Ok(Tag::Unit(label.to_string())),
+            _ => Err(format_err!("Invalid tag {}", it)),
+        }
+    }
+}
+
+/// Policy used when decoding tag sequences which are not valid in the tagging scheme, such as
+/// "I-city" following "O" in the BIO scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidTransitionPolicy {
+    /// Return an error
+    Strict,
+    /// Repair the sequence: an unexpected inside or last tag starts a new span, and a BILOU span
+    /// which is not properly terminated ends at its last tagged token
+    Lenient,
+    /// Drop the spans involved in an invalid transition
+    Discard,
+}
+
+/// Convert `(char_range, label)` spans into the tags of the tokens
+///
+/// A token belongs to the span it overlaps the most, even partially, ties being broken in favor
+/// of the first span. Spans which do not overlap any token are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::language::Language;
+/// use snips_nlu_utils::tagging::{spans_to_tags, TaggingScheme};
+/// use snips_nlu_utils::token::tokenize;
+///
+/// let tokens = tokenize("fly to new york tonight", Language::EN);
+/// let spans = vec![(7..15, "city".to_string())];
+/// let tags: Vec<String> = spans_to_tags(&spans, &tokens, TaggingScheme::BILOU)
+///     .iter()
+///     .map(|tag| tag.to_string())
+///     .collect();
+/// assert_eq!(vec!["O", "O", "B-city", "L-city", "O"], tags);
+/// ```
+pub fn spans_to_tags(
+    spans: &[(Range<usize>, String)],
+    tokens: &[Token],
+    scheme: TaggingScheme,
+) -> Vec<Tag> {
+    let span_indexes: Vec<Option<usize>> = tokens
+        .iter()
+        .map(|token| {
+            let mut best_span: Option<(usize, usize)> = None;
+            for (index, (range, _)) in spans.iter().enumerate() {
+                let overlap = intersection(range, &token.char_range)
+                    .map(|overlap| overlap.len())
+                    .unwrap_or(0);
+                if overlap > 0 && best_span.map(|(_, best)| overlap > best).unwrap_or(true) {
+                    best_span = Some((index, overlap));
+                }
+            }
+            best_span.map(|(index, _)| index)
+        })
+        .collect();
+
+    let mut tags = Vec::with_capacity(tokens.len());
+    for (token_index, span_index) in span_indexes.iter().enumerate() {
+        let span_index = match span_index {
+            Some(span_index) => *span_index,
+            None => {
+                tags.push(Tag::Outside);
+                continue;
+            }
+        };
+        let label = spans[span_index].1.clone();
+        let is_first = token_index == 0 || span_indexes[token_index - 1] != Some(span_index);
+        let is_last = span_indexes.get(token_index + 1) != Some(&Some(span_index));
+        let tag = match (scheme, is_first, is_last) {
+            (TaggingScheme::IO, _, _) => Tag::Inside(label),
+            (TaggingScheme::BIO, true, _) => Tag::Begin(label),
+            (TaggingScheme::BIO, false, _) => Tag::Inside(label),
+            (TaggingScheme::BILOU, true, true) => Tag::Unit(label),
+            (TaggingScheme::BILOU, true, false) => Tag::Begin(label),
+            (TaggingScheme::BILOU, false, true) => Tag::Last(label),
+            (TaggingScheme::BILOU, false, false) => Tag::Inside(label),
+        };
+        tags.push(tag);
+    }
+    tags
+}
+
+/// Decode the tags of the tokens into `(char_range, label)` spans
+///
+/// In the IO scheme, consecutive tokens having the same label are always merged into a single
+/// span. Tags which are not valid in the scheme are handled according to the policy.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::language::Language;
+/// use snips_nlu_utils::tagging::{tags_to_spans, InvalidTransitionPolicy, Tag, TaggingScheme};
+/// use snips_nlu_utils::token::tokenize;
+///
+/// let tokens = tokenize("fly to new york tonight", Language::EN);
+/// let tags: Vec<Tag> = vec!["O", "O", "B-city", "I-city", "I-time"]
+///     .into_iter()
+///     .map(|tag| tag.parse().unwrap())
+///     .collect();
+///
+/// let spans = tags_to_spans(
+///     &tags,
+///     &tokens,
+///     TaggingScheme::BIO,
+///     InvalidTransitionPolicy::Lenient,
+/// );
+/// assert_eq!(
+///     vec![(7..15, "city".to_string()), (16..23, "time".to_string())],
+///     spans.unwrap()
+/// );
+///
+/// let spans = tags_to_spans(
+///     &tags,
+///     &tokens,
+///     TaggingScheme::BIO,
+///     InvalidTransitionPolicy::Strict,
+/// );
+/// assert!(spans.is_err());
+/// ```
+pub fn tags_to_spans(
+    tags: &[Tag],
+    tokens: &[Token],
+    scheme: TaggingScheme,
+    policy: InvalidTransitionPolicy,
+) -> Result<Vec<(Range<usize>, String)>, failure::Error> {
+    if tags.len() != tokens.len() {
+        return Err(format_err!(
+            "Expected {} tags, one per token, but found {}",
+            tokens.len(),
+            tags.len()
+        ));
+    }
+    let mut decoder = SpanDecoder {
+        tokens,
+        scheme,
+        policy,
+        current: None,
+        spans: vec![],
+    };
+    for (index, tag) in tags.iter().enumerate() {
+        let is_allowed = decoder.check(tag.is_allowed_by(scheme), || {
+            format!(
+                "Tag {} at index {} is not allowed in the {:?} scheme",
+                tag, index, scheme
+            )
+        })?;
+        let continues_current = match (&decoder.current, tag.label()) {
+            (Some(current), Some(label)) => current.label == label && !current.is_terminated,
+            _ => false,
+        };
+        match tag {
+            Tag::Outside => decoder.close()?,
+            Tag::Begin(label) => decoder.open(index, label, is_allowed, false)?,
+            Tag::Unit(label) => decoder.open(index, label, is_allowed, true)?,
+            Tag::Inside(_) | Tag::Last(_) if continues_current => {
+                if let Some(current) = decoder.current.as_mut() {
+                    current.end = index;
+                    current.is_valid &= is_allowed;
+                    current.is_terminated = matches!(tag, Tag::Last(_));
+                }
+            }
+            Tag::Inside(label) if scheme == TaggingScheme::IO => {
+                decoder.open(index, label, is_allowed, false)?
+            }
+            Tag::Inside(label) | Tag::Last(label) => {
+                let previous = index
+                    .checked_sub(1)
+                    .map(|previous| tags[previous].to_string())
+                    .unwrap_or_else(|| "the start".to_string());
+                let is_valid = decoder.check(false, || {
+                    format!("Tag {} at index {} cannot follow {}", tag, index, previous)
+                })?;
+                let is_terminated = matches!(tag, Tag::Last(_));
+                decoder.open(index, label, is_allowed && is_valid, is_terminated)?
+            }
+        }
+    }
+    decoder.close()?;
+    Ok(decoder.spans)
+}
+
+struct OpenSpan {
+    start: usize,
+    end: usize,
+    label: String,
+    is_valid: bool,
+    is_terminated: bool,
+}
+
+struct SpanDecoder<'a> {
+    tokens: &'a [Token],
+    scheme: TaggingScheme,
+    policy: InvalidTransitionPolicy,
+    current: Option<OpenSpan>,
+    spans: Vec<(Range<usize>, String)>,
+}
+
+impl<'a> SpanDecoder<'a> {
+    /// Apply the policy to a transition, and return whether the span involved should be kept
+    fn check<F: Fn() -> String>(&self, is_valid: bool, message: F) -> Result<bool, failure::Error> {
+        if is_valid {
+            return Ok(true);
+        }
+        match self.policy {
+            InvalidTransitionPolicy::Strict => Err(format_err!("{}", message())),
+            InvalidTransitionPolicy::Lenient => Ok(true),
+            InvalidTransitionPolicy::Discard => Ok(false),
+        }
+    }
+
+    fn open(
+        &mut self,
+        index: usize,
+        label: &str,
+        is_valid: bool,
+        is_terminated: bool,
+    ) -> Result<(), failure::Error> {
+        self.close()?;
+        self.current = Some(OpenSpan {
+            start: index,
+            end: index,
+            label: label.to_string(),
+            is_valid,
+            is_terminated,
+        });
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<(), failure::Error> {
+        let span = match self.current.take() {
+            Some(span) => span,
+            None => return Ok(()),
+        };
+        let is_terminated = span.is_terminated || self.scheme != TaggingScheme::BILOU;
+        let is_valid = self.check(is_terminated, || {
+            format!(
+                "Span {} starting at index {} is not terminated by a last tag",
+                span.label, span.start
+            )
+        })?;
+        if span.is_valid && is_valid {
+            let char_range =
+                self.tokens[span.start].char_range.start..self.tokens[span.end].char_range.end;
+            self.spans.push((char_range, span.label));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language;
+    use crate::token::tokenize;
+
+    fn parse_tags(tags: &[&str]) -> Vec<Tag> {
+        tags.iter().map(|tag| tag.parse().unwrap()).collect()
+    }
+
+    fn spans(spans: &[(Range<usize>, &str)]) -> Vec<(Range<usize>, String)> {
+        spans
+            .iter()
+            .map(|(range, label)| (range.clone(), label.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn spans_to_tags_should_encode_spans_in_each_scheme() {
+        // Given
+        let tokens = tokenize("book a table for 4 people in paris", Language::EN);
+        let spans = spans(&[(17..25, "party_size"), (29..34, "city")]);
+
+        // When
+        let io_tags = spans_to_tags(&spans, &tokens, TaggingScheme::IO);
+        let bio_tags = spans_to_tags(&spans, &tokens, TaggingScheme::BIO);
+        let bilou_tags = spans_to_tags(&spans, &tokens, TaggingScheme::BILOU);
+
+        // Then
+        let o = "O";
+        assert_eq!(
+            parse_tags(&[o, o, o, o, "I-party_size", "I-party_size", o, "I-city"]),
+            io_tags
+        );
+        assert_eq!(
+            parse_tags(&[o, o, o, o, "B-party_size", "I-party_size", o, "B-city"]),
+            bio_tags
+        );
+        assert_eq!(
+            parse_tags(&[o, o, o, o, "B-party_size", "L-party_size", o, "U-city"]),
+            bilou_tags
+        );
+    }
+
+    #[test]
+    fn spans_to_tags_should_handle_partial_token_overlaps() {
+        // Given
+        let tokens = tokenize("from newyork tomorrow", Language::EN);
+        let spans = spans(&[(3..8, "origin"), (8..12, "city"), (13..15, "date")]);
+
+        // When
+        let tags = spans_to_tags(&spans, &tokens, TaggingScheme::BIO);
+
+        // Then
+        assert_eq!(parse_tags(&["B-origin", "B-city", "B-date"]), tags);
+    }
+
+    #[test]
+    fn tags_to_spans_should_reverse_spans_to_tags() {
+        // Given
+        let tokens = tokenize("play the beatles then the rolling stones", Language::EN);
+        let spans = spans(&[(9..16, "artist"), (22..40, "artist")]);
+
+        // Then
+        for scheme in &[TaggingScheme::BIO, TaggingScheme::BILOU] {
+            let tags = spans_to_tags(&spans, &tokens, *scheme);
+            let decoded =
+                tags_to_spans(&tags, &tokens, *scheme, InvalidTransitionPolicy::Strict).unwrap();
+            assert_eq!(spans, decoded);
+        }
+    }
+
+    #[test]
+    fn tags_to_spans_should_apply_invalid_transition_policy() {
+        // Given
+        let tokens = tokenize("from paris to new york", Language::EN);
+        let tags = parse_tags(&["O", "B-city", "O", "I-city", "L-city"]);
+
+        // When
+        let strict = tags_to_spans(
+            &tags,
+            &tokens,
+            TaggingScheme::BILOU,
+            InvalidTransitionPolicy::Strict,
+        );
+        let lenient = tags_to_spans(
+            &tags,
+            &tokens,
+            TaggingScheme::BILOU,
+            InvalidTransitionPolicy::Lenient,
+        )
+        .unwrap();
+        let discarded = tags_to_spans(
+            &tags,
+            &tokens,
+            TaggingScheme::BILOU,
+            InvalidTransitionPolicy::Discard,
+        )
+        .unwrap();
+
+        // Then
+        assert!(strict.is_err());
+        assert_eq!(spans(&[(5..10, "city"), (14..22, "city")]), lenient);
+        assert!(discarded.is_empty());
+    }
+
+    #[test]
+    fn tags_to_spans_should_merge_io_tags() {
+        // Given
+        let tokens = tokenize("new york paris", Language::EN);
+        let tags = parse_tags(&["I-city", "I-city", "B-city"]);
+
+        // When
+        let lenient = tags_to_spans(
+            &tags,
+            &tokens,
+            TaggingScheme::IO,
+            InvalidTransitionPolicy::Lenient,
+        );
+        let strict = tags_to_spans(
+            &tags,
+            &tokens,
+            TaggingScheme::IO,
+            InvalidTransitionPolicy::Strict,
+        );
+
+        // Then
+        assert_eq!(spans(&[(0..8, "city"), (9..14, "city")]), lenient.unwrap());
+        assert!(strict.is_err());
+        assert!(tags_to_spans(
+            &tags[..2],
+            &tokens,
+            TaggingScheme::IO,
+            InvalidTransitionPolicy::Lenient
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn tag_should_be_parsed_and_formatted() {
+        assert_eq!(
+            Ok(Tag::Last("city".to_string())),
+            "L-city".parse().map_err(|_| ())
+        );
+        assert_eq!("U-date", Tag::Unit("date".to_string()).to_string());
+        assert!("X-city".parse::<Tag>().is_err());
+        assert!("B-".parse::<Tag>().is_err());
+    }
+}
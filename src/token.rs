@@ -6,8 +6,10 @@ use lazy_static::lazy_static;
 use regex::{Regex, RegexBuilder};
 
 use crate::language::Language;
+use crate::phonetic::phonetic_key;
 use crate::range::ranges_overlap;
-use crate::string::{convert_to_char_range, normalize};
+use crate::offset::OffsetIndex;
+use crate::string::normalize;
 
 pub type Ngram = (String, Vec<usize>);
 
@@ -34,6 +36,11 @@ impl Token {
             self._normalized.clone().unwrap()
         }
     }
+
+    /// Phonetic key of the token value, see `phonetic::phonetic_key`
+    pub fn phonetic_key(&self, language: Language) -> Option<String> {
+        phonetic_key(&self.value, language)
+    }
 }
 
 pub fn tokenize(input: &str, language: Language) -> Vec<Token> {
@@ -54,6 +61,7 @@ pub fn tokenize_light(input: &str, language: Language) -> Vec<String> {
 
 fn _regex_tokenization(input: &str, regexes: &[&Regex]) -> Vec<Token> {
     let mut non_overlapping_tokens: Vec<Token> = vec![];
+    let offsets = OffsetIndex::new(input);
 
     for r in regexes {
         let mut tokens: Vec<Token> = r
@@ -62,7 +70,7 @@ fn _regex_tokenization(input: &str, regexes: &[&Regex]) -> Vec<Token> {
                 let range = m.start()..m.end();
                 let value = m.as_str().to_string();
                 Token {
-                    char_range: convert_to_char_range(input, &range),
+                    char_range: offsets.byte_range_to_char_range(&range),
                     value,
                     range,
                     _normalized: None
@@ -236,6 +244,28 @@ mod tests {
         assert_eq!(retrieved, expected);
     }
 
+    #[test]
+    fn tokenize_should_handle_cyrillic_and_diacritics() {
+        let test_cases = vec![
+            (Language::RU, "Привет, мир! Ёлка стоит 100₽", vec!["Привет", "мир", "!", "Ёлка", "стоит", "100", "₽"]),
+            (Language::PL, "Zażółć gęślą jaźń", vec!["Zażółć", "gęślą", "jaźń"]),
+            (Language::PL, "z\u{0307}o\u{0301}łw", vec!["z\u{0307}o\u{0301}łw"]),
+            (Language::NL, "Hoeveel kost het?", vec!["Hoeveel", "kost", "het", "?"]),
+            (Language::SV, "Två smörgåsar, tack!", vec!["Två", "smörgåsar", "tack", "!"]),
+            (Language::TR, "Iğdır'da güzel bir gün!", vec!["Iğdır", "da", "güzel", "bir", "gün", "!"]),
+        ];
+
+        for (language, text, expected_tokens) in test_cases {
+            assert_eq!(expected_tokens, tokenize_light(text, language));
+        }
+
+        let char_ranges: Vec<Range<usize>> = tokenize("Zażółć gęślą jaźń", Language::PL)
+            .into_iter()
+            .map(|t| t.char_range)
+            .collect();
+        assert_eq!(vec![0..6, 7..12, 13..17], char_ranges);
+    }
+
     #[test]
     fn compute_all_ngrams_works() {
         let result = compute_all_ngrams(&vec!["a", "b", "c"], 3);
@@ -0,0 +1,533 @@
+use std::ops::Range;
+
+use fnv::FnvHashMap;
+use lazy_static::lazy_static;
+use unicode_normalization::char::{decompose_canonical, is_combining_mark};
+
+use crate::alignment::Alignment;
+use crate::hangul::{decompose_hangul_syllable, is_hangul_syllable};
+use crate::japanese::to_hiragana;
+
+/// Result of a transliteration, along with its alignment with the source string
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transliteration {
+    pub value: String,
+    pub alignment: Alignment,
+}
+
+/// Transliterate non-latin scripts into latin characters
+///
+/// The following scripts are supported:
+///
+/// - Cyrillic, with russian, ukrainian, belarusian and serbian letters
+/// - Greek, with ELOT 743 digraphs such as "ου" and "αυ"
+/// - Hiragana and Katakana, using Hepburn romanization without macrons, so that long vowels are
+///   written as they are spelled
+/// - Hangul, using the Revised Romanization of Korean with final consonants carried over to the
+///   next syllable when it starts with a vowel
+///
+/// Other characters are kept as is.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::transliteration::transliterate;
+///
+/// let result = transliterate("Чайковский");
+/// assert_eq!("Chaykovskiy", result.value);
+/// assert_eq!(0..2, result.alignment.original_byte_range(&(0..2)));
+/// assert_eq!("Athina", transliterate("Αθήνα").value);
+/// assert_eq!("toukyou", transliterate("とうきょう").value);
+/// assert_eq!("hangugeo", transliterate("한국어").value);
+/// ```
+pub fn transliterate(input: &str) -> Transliteration {
+    let chars: Vec<(usize, char)> = input.char_indices().collect();
+    let mut transliterator = Transliterator::new(input);
+    let mut index = 0;
+    while index < chars.len() {
+        let (byte_index, c) = chars[index];
+        let next_char = chars.get(index + 1).map(|(_, c)| *c);
+        let source_end = |nb_chars: usize| {
+            chars
+                .get(index + nb_chars)
+                .map(|(i, _)| *i)
+                .unwrap_or_else(|| input.len())
+        };
+        let nb_consumed = if is_kana(c) {
+            transliterator.push_kana(c, next_char, byte_index, source_end(2))
+        } else if is_hangul_syllable(c) {
+            transliterator.push_hangul(c, next_char, byte_index..source_end(1));
+            1
+        } else {
+            let digraph = next_char.and_then(|next| lookup(&[c, next]).map(|latin| (latin, 2)));
+            match digraph.or_else(|| lookup(&[c]).map(|latin| (latin, 1))) {
+                Some((latin, nb_chars)) => {
+                    let range = byte_index..source_end(nb_chars);
+                    if c.is_uppercase() {
+                        transliterator.push(&capitalize(latin), range);
+                    } else {
+                        transliterator.push(latin, range);
+                    }
+                    nb_chars
+                }
+                None => {
+                    transliterator.push(c.encode_utf8(&mut [0; 4]), byte_index..source_end(1));
+                    1
+                }
+            }
+        };
+        index += nb_consumed;
+    }
+    transliterator.finish()
+}
+
+struct Transliterator<'a> {
+    input: &'a str,
+    value: String,
+    alignment: Alignment,
+    /// Source range of a pending small "tsu" (sokuon), which doubles the next consonant
+    pending_sokuon: Option<Range<usize>>,
+    /// Whether the last pushed char is a hangul syllable ending with "ㄹ"
+    rieul_final: bool,
+}
+
+impl<'a> Transliterator<'a> {
+    fn new(input: &'a str) -> Self {
+        Transliterator {
+            input,
+            value: String::with_capacity(input.len()),
+            alignment: Alignment::default(),
+            pending_sokuon: None,
+            rieul_final: false,
+        }
+    }
+
+    fn push(&mut self, latin: &str, source_range: Range<usize>) {
+        self.rieul_final = false;
+        if let Some(sokuon_range) = self.pending_sokuon.take() {
+            let doubled = if latin.starts_with("ch") {
+                "t"
+            } else {
+                latin
+                    .get(0..1)
+                    .filter(|c| c.chars().all(|c| c.is_ascii_alphabetic() && !is_latin_vowel(c)))
+                    .unwrap_or("")
+            };
+            self.value.push_str(doubled);
+            self.alignment.push(&self.input[sokuon_range], doubled);
+        }
+        self.value.push_str(latin);
+        self.alignment.push(&self.input[source_range], latin);
+    }
+
+    /// Returns the number of consumed chars
+    fn push_kana(&mut self, c: char, next_char: Option<char>, start: usize, end: usize) -> usize {
+        let hiragana = to_hiragana(c);
+        if hiragana == 'っ' {
+            let range = start..start + c.len_utf8();
+            self.pending_sokuon = Some(range);
+            return 1;
+        }
+        if c == 'ー' {
+            // The long vowel mark repeats the previous vowel
+            let vowel = self.value.chars().last().filter(|c| is_latin_vowel(*c));
+            match vowel {
+                Some(vowel) => self.push(vowel.encode_utf8(&mut [0; 4]), start..start + c.len_utf8()),
+                None => self.push("", start..start + c.len_utf8()),
+            }
+            return 1;
+        }
+        let base = match KANA_TABLE.get(&hiragana) {
+            Some(base) => *base,
+            None => {
+                self.push(c.encode_utf8(&mut [0; 4]), start..start + c.len_utf8());
+                return 1;
+            }
+        };
+        let combined = next_char.and_then(|next| combine_kana(base, to_hiragana(next)));
+        match combined {
+            Some(combined) => {
+                self.push(&combined, start..end);
+                2
+            }
+            None => {
+                self.push(base, start..start + c.len_utf8());
+                1
+            }
+        }
+    }
+
+    fn push_hangul(&mut self, c: char, next_char: Option<char>, source_range: Range<usize>) {
+        let (initial, medial, final_consonant) = decompose_hangul_syllable(c);
+        let next_initial = next_char
+            .filter(|c| is_hangul_syllable(*c))
+            .map(|c| decompose_hangul_syllable(c).0);
+        let mut latin = String::new();
+        latin.push_str(HANGUL_INITIALS[initial]);
+        latin.push_str(HANGUL_MEDIALS[medial]);
+        if let Some(final_index) = final_consonant {
+            // Final consonants are carried over when the next syllable starts with a vowel,
+            // "ㅇ" being the silent initial
+            let carried_over = next_initial == Some(HANGUL_SILENT_INITIAL)
+                && HANGUL_FINALS_BEFORE_VOWEL[final_index] != HANGUL_FINALS[final_index];
+            if carried_over {
+                latin.push_str(HANGUL_FINALS_BEFORE_VOWEL[final_index]);
+            } else if HANGUL_FINALS[final_index] == "l" && next_initial == Some(HANGUL_RIEUL) {
+                latin.push('l');
+            } else {
+                latin.push_str(HANGUL_FINALS[final_index]);
+            }
+        }
+        if self.rieul_final && initial == HANGUL_RIEUL {
+            latin.replace_range(0..1, "l");
+        }
+        self.push(&latin, source_range);
+        self.rieul_final = final_consonant == Some(HANGUL_FINAL_RIEUL);
+    }
+
+    fn finish(mut self) -> Transliteration {
+        if let Some(sokuon_range) = self.pending_sokuon.take() {
+            self.alignment.push(&self.input[sokuon_range], "");
+        }
+        Transliteration {
+            value: self.value,
+            alignment: self.alignment,
+        }
+    }
+}
+
+fn capitalize(latin: &str) -> String {
+    let mut chars = latin.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Look up the lowercased chars in the table, and then the lowercased chars without their
+/// combining marks, so that e.g. "Ά" is found as "α" while "й" is not confused with "и"
+fn lookup(chars: &[char]) -> Option<&'static str> {
+    let lowercase: String = chars.iter().flat_map(|c| c.to_lowercase()).collect();
+    TABLE.get(&*lowercase).cloned().or_else(|| {
+        let mut base = String::new();
+        for c in lowercase.chars() {
+            decompose_canonical(c, |d| {
+                if !is_combining_mark(d) {
+                    base.push(d)
+                }
+            });
+        }
+        TABLE.get(&*base).cloned()
+    })
+}
+
+fn is_latin_vowel(c: char) -> bool {
+    "aeiou".contains(c)
+}
+
+fn is_kana(c: char) -> bool {
+    ('\u{3041}'..='\u{3096}').contains(&c) || ('\u{30A1}'..='\u{30FA}').contains(&c) || c == 'ー'
+}
+
+/// Combine a kana with a following small kana, e.g. "きゃ" gives "kya" and "ファ" gives "fa"
+fn combine_kana(base: &str, small_kana: char) -> Option<String> {
+    let (consonant, vowel) = base.split_at(base.len() - 1);
+    match small_kana {
+        'ゃ' | 'ゅ' | 'ょ' if vowel == "i" && !consonant.is_empty() => {
+            let small_vowel = &KANA_TABLE[&small_kana][1..];
+            if consonant == "sh" || consonant == "ch" || consonant == "j" {
+                Some(format!("{}{}", consonant, small_vowel))
+            } else {
+                Some(format!("{}y{}", consonant, small_vowel))
+            }
+        }
+        'ぁ' | 'ぃ' | 'ぅ' | 'ぇ' | 'ぉ' => {
+            let consonant = if consonant.is_empty() && vowel == "u" { "w" } else { consonant };
+            if consonant.is_empty() {
+                return None;
+            }
+            Some(format!("{}{}", consonant, KANA_TABLE[&small_kana]))
+        }
+        _ => None,
+    }
+}
+
+const HANGUL_SILENT_INITIAL: usize = 11;
+const HANGUL_RIEUL: usize = 5;
+const HANGUL_FINAL_RIEUL: usize = 7;
+
+const HANGUL_INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p",
+    "h",
+];
+
+const HANGUL_MEDIALS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we",
+    "wi", "yu", "eu", "ui", "i",
+];
+
+const HANGUL_FINALS: [&str; 27] = [
+    "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p", "p", "t",
+    "t", "ng", "t", "t", "k", "t", "p", "t",
+];
+
+const HANGUL_FINALS_BEFORE_VOWEL: [&str; 27] = [
+    "g", "kk", "ks", "n", "nj", "n", "d", "r", "lg", "lm", "lb", "ls", "lt", "lp", "r", "m", "b",
+    "ps", "s", "ss", "ng", "j", "ch", "k", "t", "p", "",
+];
+
+lazy_static! {
+    static ref TABLE: FnvHashMap<&'static str, &'static str> = CYRILLIC_TABLE
+        .iter()
+        .chain(GREEK_TABLE.iter())
+        .cloned()
+        .collect();
+    static ref KANA_TABLE: FnvHashMap<char, &'static str> = HIRAGANA_TABLE.iter().cloned().collect();
+}
+
+const CYRILLIC_TABLE: &[(&str, &str)] = &[
+    ("а", "a"),
+    ("б", "b"),
+    ("в", "v"),
+    ("г", "g"),
+    ("д", "d"),
+    ("е", "e"),
+    ("ё", "yo"),
+    ("ж", "zh"),
+    ("з", "z"),
+    ("и", "i"),
+    ("й", "y"),
+    ("к", "k"),
+    ("л", "l"),
+    ("м", "m"),
+    ("н", "n"),
+    ("о", "o"),
+    ("п", "p"),
+    ("р", "r"),
+    ("с", "s"),
+    ("т", "t"),
+    ("у", "u"),
+    ("ф", "f"),
+    ("х", "kh"),
+    ("ц", "ts"),
+    ("ч", "ch"),
+    ("ш", "sh"),
+    ("щ", "shch"),
+    ("ъ", ""),
+    ("ы", "y"),
+    ("ь", ""),
+    ("э", "e"),
+    ("ю", "yu"),
+    ("я", "ya"),
+    ("і", "i"),
+    ("ї", "yi"),
+    ("є", "ye"),
+    ("ґ", "g"),
+    ("ў", "u"),
+    ("ђ", "dj"),
+    ("ј", "j"),
+    ("љ", "lj"),
+    ("њ", "nj"),
+    ("ћ", "c"),
+    ("џ", "dz"),
+];
+
+const GREEK_TABLE: &[(&str, &str)] = &[
+    ("αυ", "av"),
+    ("ευ", "ev"),
+    ("ηυ", "iv"),
+    ("ου", "ou"),
+    ("γγ", "ng"),
+    ("γξ", "nx"),
+    ("γχ", "nch"),
+    ("α", "a"),
+    ("β", "v"),
+    ("γ", "g"),
+    ("δ", "d"),
+    ("ε", "e"),
+    ("ζ", "z"),
+    ("η", "i"),
+    ("θ", "th"),
+    ("ι", "i"),
+    ("κ", "k"),
+    ("λ", "l"),
+    ("μ", "m"),
+    ("ν", "n"),
+    ("ξ", "x"),
+    ("ο", "o"),
+    ("π", "p"),
+    ("ρ", "r"),
+    ("σ", "s"),
+    ("ς", "s"),
+    ("τ", "t"),
+    ("υ", "y"),
+    ("φ", "f"),
+    ("χ", "ch"),
+    ("ψ", "ps"),
+    ("ω", "o"),
+];
+
+/// Katakana are converted to hiragana before being looked up in this table
+const HIRAGANA_TABLE: &[(char, &str)] = &[
+    ('ぁ', "a"),
+    ('あ', "a"),
+    ('ぃ', "i"),
+    ('い', "i"),
+    ('ぅ', "u"),
+    ('う', "u"),
+    ('ぇ', "e"),
+    ('え', "e"),
+    ('ぉ', "o"),
+    ('お', "o"),
+    ('か', "ka"),
+    ('が', "ga"),
+    ('き', "ki"),
+    ('ぎ', "gi"),
+    ('く', "ku"),
+    ('ぐ', "gu"),
+    ('け', "ke"),
+    ('げ', "ge"),
+    ('こ', "ko"),
+    ('ご', "go"),
+    ('さ', "sa"),
+    ('ざ', "za"),
+    ('し', "shi"),
+    ('じ', "ji"),
+    ('す', "su"),
+    ('ず', "zu"),
+    ('せ', "se"),
+    ('ぜ', "ze"),
+    ('そ', "so"),
+    ('ぞ', "zo"),
+    ('た', "ta"),
+    ('だ', "da"),
+    ('ち', "chi"),
+    ('ぢ', "ji"),
+    ('つ', "tsu"),
+    ('づ', "zu"),
+    ('て', "te"),
+    ('で', "de"),
+    ('と', "to"),
+    ('ど', "do"),
+    ('な', "na"),
+    ('に', "ni"),
+    ('ぬ', "nu"),
+    ('ね', "ne"),
+    ('の', "no"),
+    ('は', "ha"),
+    ('ば', "ba"),
+    ('ぱ', "pa"),
+    ('ひ', "hi"),
+    ('び', "bi"),
+    ('ぴ', "pi"),
+    ('ふ', "fu"),
+    ('ぶ', "bu"),
+    ('ぷ', "pu"),
+    ('へ', "he"),
+    ('べ', "be"),
+    ('ぺ', "pe"),
+    ('ほ', "ho"),
+    ('ぼ', "bo"),
+    ('ぽ', "po"),
+    ('ま', "ma"),
+    ('み', "mi"),
+    ('む', "mu"),
+    ('め', "me"),
+    ('も', "mo"),
+    ('ゃ', "ya"),
+    ('や', "ya"),
+    ('ゅ', "yu"),
+    ('ゆ', "yu"),
+    ('ょ', "yo"),
+    ('よ', "yo"),
+    ('ら', "ra"),
+    ('り', "ri"),
+    ('る', "ru"),
+    ('れ', "re"),
+    ('ろ', "ro"),
+    ('ゎ', "wa"),
+    ('わ', "wa"),
+    ('ゐ', "i"),
+    ('ゑ', "e"),
+    ('を', "o"),
+    ('ん', "n"),
+    ('ゔ', "vu"),
+    ('ゕ', "ka"),
+    ('ゖ', "ke"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transliterate_cyrillic_works() {
+        assert_eq!("Moskva", transliterate("Москва").value);
+        assert_eq!("Pyotr Ilich", transliterate("Пётр Ильич").value);
+        assert_eq!("Shchukin", transliterate("Щукин").value);
+        assert_eq!("Ukrayina", transliterate("Україна").value);
+    }
+
+    #[test]
+    fn transliterate_greek_works() {
+        assert_eq!("Evangelia", transliterate("Ευαγγελία").value);
+        assert_eq!("Mousiki", transliterate("Μουσική").value);
+        assert_eq!("Sokratis", transliterate("Σωκράτης").value);
+    }
+
+    #[test]
+    fn transliterate_kana_works() {
+        assert_eq!("kyouto", transliterate("きょうと").value);
+        assert_eq!("shashin", transliterate("しゃしん").value);
+        assert_eq!("kitte", transliterate("きって").value);
+        assert_eq!("matcha", transliterate("まっちゃ").value);
+        assert_eq!("koohii", transliterate("コーヒー").value);
+        assert_eq!("faito", transliterate("ファイト").value);
+        assert_eq!("uisukii", transliterate("ウイスキー").value);
+        assert_eq!("wisukii", transliterate("ウィスキー").value);
+    }
+
+    #[test]
+    fn transliterate_hangul_works() {
+        assert_eq!("hangugeo", transliterate("한국어").value);
+        assert_eq!("seoul", transliterate("서울").value);
+        assert_eq!("mullae", transliterate("물래").value);
+        assert_eq!("annyeong haseyo", transliterate("안녕 하세요").value);
+    }
+
+    #[test]
+    fn transliterate_should_keep_other_chars() {
+        assert_eq!("Hello, Moskva!", transliterate("Hello, Москва!").value);
+    }
+
+    #[test]
+    fn alignment_works() {
+        // Given
+        let input = "play Чайковский";
+
+        // When
+        let result = transliterate(input);
+
+        // Then
+        assert_eq!("play Chaykovskiy", result.value);
+        assert_eq!(5..25, result.alignment.original_byte_range(&(5..16)));
+        assert_eq!("Чай", &input[result.alignment.original_byte_range(&(5..9))]);
+        assert_eq!("к", &input[result.alignment.original_byte_range(&(9..10))]);
+        assert_eq!(5..9, result.alignment.normalized_char_range(&(5..8)));
+    }
+
+    #[test]
+    fn alignment_should_handle_sokuon() {
+        // Given
+        let input = "きって";
+
+        // When
+        let result = transliterate(input);
+
+        // Then
+        assert_eq!("kitte", result.value);
+        assert_eq!("っ", &input[result.alignment.original_byte_range(&(2..3))]);
+        assert_eq!("て", &input[result.alignment.original_byte_range(&(3..5))]);
+        assert_eq!(0..0, transliterate("ーっ").alignment.normalized_char_range(&(0..2)));
+    }
+}
@@ -0,0 +1,560 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, Read, Write};
+use std::path::Path;
+
+use failure::{format_err, ResultExt};
+use fnv::FnvHashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::hashing::murmurhash3_32;
+use crate::language::Language;
+use crate::string::normalize;
+use crate::token::{compute_all_ngrams, tokenize};
+
+/// Sparse vector, with indices sorted in increasing order
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SparseVector {
+    pub indices: Vec<usize>,
+    pub values: Vec<f64>,
+}
+
+/// Sparse matrix in the Compressed Sparse Row format, as used by `scipy.sparse.csr_matrix`
+///
+/// The column indices and values of row `i` are stored in `indices[indptr[i]..indptr[i + 1]]`
+/// and `data[indptr[i]..indptr[i + 1]]`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CsrMatrix {
+    pub indptr: Vec<usize>,
+    pub indices: Vec<usize>,
+    pub data: Vec<f64>,
+    pub nb_columns: usize,
+}
+
+impl CsrMatrix {
+    pub fn from_rows(rows: Vec<SparseVector>, nb_columns: usize) -> Self {
+        let mut matrix = CsrMatrix {
+            indptr: vec![0],
+            nb_columns,
+            ..Default::default()
+        };
+        for row in rows {
+            matrix.indices.extend(row.indices);
+            matrix.data.extend(row.values);
+            matrix.indptr.push(matrix.indices.len());
+        }
+        matrix
+    }
+
+    pub fn nb_rows(&self) -> usize {
+        self.indptr.len() - 1
+    }
+}
+
+/// Configuration of a `HashingVectorizer`, whose default values match the ones of
+/// scikit-learn's `HashingVectorizer`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HashingVectorizerConfig {
+    pub nb_buckets: usize,
+    /// Ngrams of sizes 1 to `max_ngram_size` are used as features
+    pub max_ngram_size: usize,
+    /// Use the sign of the hash as the sign of the feature value, so that collisions tend to
+    /// cancel out instead of accumulating
+    pub signed: bool,
+    pub l2_normalize: bool,
+    /// Normalize tokens with `string::normalize` before computing ngrams
+    pub normalize_tokens: bool,
+}
+
+impl Default for HashingVectorizerConfig {
+    fn default() -> Self {
+        HashingVectorizerConfig {
+            nb_buckets: 1 << 20,
+            max_ngram_size: 1,
+            signed: true,
+            l2_normalize: true,
+            normalize_tokens: true,
+        }
+    }
+}
+
+/// Vectorizer which maps the ngrams of an utterance to feature indices with a hash function,
+/// and hence requires no vocabulary
+///
+/// Ngrams are hashed with the signed MurmurHash3 x86 32-bit function, as done by scikit-learn,
+/// so that the same tokens give the same features with both libraries.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::language::Language;
+/// use snips_nlu_utils::vectorizer::{HashingVectorizer, HashingVectorizerConfig};
+///
+/// let config = HashingVectorizerConfig {
+///     nb_buckets: 16,
+///     l2_normalize: false,
+///     ..Default::default()
+/// };
+/// let vectorizer = HashingVectorizer::new(config, Language::EN).unwrap();
+/// let vector = vectorizer.transform("Foo bar foo");
+/// assert_eq!(vec![0, 13], vector.indices);
+/// assert_eq!(vec![-2.0, 1.0], vector.values);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HashingVectorizer {
+    config: HashingVectorizerConfig,
+    language: Language,
+}
+
+impl HashingVectorizer {
+    /// Fail when the config has no bucket
+    pub fn new(
+        config: HashingVectorizerConfig,
+        language: Language,
+    ) -> Result<Self, failure::Error> {
+        if config.nb_buckets == 0 {
+            return Err(format_err!("Number of buckets must be positive"));
+        }
+        Ok(HashingVectorizer { config, language })
+    }
+
+    pub fn config(&self) -> &HashingVectorizerConfig {
+        &self.config
+    }
+
+    pub fn transform(&self, input: &str) -> SparseVector {
+        let mut features: FnvHashMap<usize, f64> = FnvHashMap::default();
+        for ngram in ngrams(
+            input,
+            self.language,
+            self.config.max_ngram_size,
+            self.config.normalize_tokens,
+        ) {
+            let hash = murmurhash3_32(&ngram, 0);
+            let index = (i64::from(hash).abs() % self.config.nb_buckets as i64) as usize;
+            let value = if self.config.signed && hash < 0 {
+                -1.0
+            } else {
+                1.0
+            };
+            *features.entry(index).or_insert(0.0) += value;
+        }
+        let mut vector = sparse_vector(features);
+        if self.config.l2_normalize {
+            l2_normalize(&mut vector.values);
+        }
+        vector
+    }
+
+    pub fn transform_all<S: AsRef<str>>(&self, inputs: &[S]) -> CsrMatrix {
+        let rows = inputs
+            .iter()
+            .map(|input| self.transform(input.as_ref()))
+            .collect();
+        CsrMatrix::from_rows(rows, self.config.nb_buckets)
+    }
+}
+
+/// Configuration of a `TfidfVectorizer`, whose default values match the ones of scikit-learn's
+/// `TfidfVectorizer`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TfidfVectorizerConfig {
+    /// Ngrams of sizes 1 to `max_ngram_size` are used as features
+    pub max_ngram_size: usize,
+    /// Normalize tokens with `string::normalize` before computing ngrams
+    pub normalize_tokens: bool,
+    /// Replace the term frequency `tf` with `1 + ln(tf)`
+    pub sublinear_tf: bool,
+    /// Add one to the document frequencies, as if an extra document contained every ngram once,
+    /// which prevents divisions by zero
+    pub smooth_idf: bool,
+    /// Ngrams which appear in strictly less than `min_df` documents are ignored
+    pub min_df: usize,
+    /// Ngrams which appear in strictly more than this proportion of the documents are ignored
+    pub max_df: f64,
+    pub l2_normalize: bool,
+}
+
+impl Default for TfidfVectorizerConfig {
+    fn default() -> Self {
+        TfidfVectorizerConfig {
+            max_ngram_size: 1,
+            normalize_tokens: true,
+            sublinear_tf: false,
+            smooth_idf: true,
+            min_df: 1,
+            max_df: 1.0,
+            l2_normalize: true,
+        }
+    }
+}
+
+/// TF-IDF vectorizer, whose vocabulary and inverse document frequencies are fitted on a corpus
+///
+/// The fitted vectorizer can be serialized to JSON, in order to be shared between the training
+/// and the inference.
+///
+/// # Examples
+///
+/// ```
+/// use snips_nlu_utils::language::Language;
+/// use snips_nlu_utils::vectorizer::{TfidfVectorizer, TfidfVectorizerConfig};
+///
+/// let corpus = ["turn on the lights", "turn off the lights", "what is the weather"];
+/// let config = TfidfVectorizerConfig::default();
+/// let vectorizer = TfidfVectorizer::fit(config, &corpus, Language::EN).unwrap();
+/// assert_eq!(Some(&3), vectorizer.vocabulary().get("on"));
+///
+/// let vector = vectorizer.transform("Turn ON the kitchen lights");
+/// assert_eq!(vec![1, 3, 4, 5], vector.indices);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TfidfVectorizer {
+    config: TfidfVectorizerConfig,
+    language: Language,
+    vocabulary: BTreeMap<String, usize>,
+    idf: Vec<f64>,
+}
+
+impl TfidfVectorizer {
+    /// Build the vocabulary and compute the inverse document frequencies of the corpus
+    ///
+    /// Vocabulary indices follow the alphabetical order of the ngrams. An error is returned
+    /// when no ngram remains after applying the document frequency bounds.
+    pub fn fit<S: AsRef<str>>(
+        config: TfidfVectorizerConfig,
+        corpus: &[S],
+        language: Language,
+    ) -> Result<Self, failure::Error> {
+        if config.max_df < 0.0 || config.max_df > 1.0 {
+            return Err(format_err!(
+                "Invalid max_df {}, it must be between 0 and 1",
+                config.max_df
+            ));
+        }
+        let mut document_frequencies: BTreeMap<String, usize> = BTreeMap::new();
+        for document in corpus {
+            let mut document_ngrams = ngrams(
+                document.as_ref(),
+                language,
+                config.max_ngram_size,
+                config.normalize_tokens,
+            );
+            document_ngrams.sort();
+            document_ngrams.dedup();
+            for ngram in document_ngrams {
+                *document_frequencies.entry(ngram).or_insert(0) += 1;
+            }
+        }
+        let nb_documents = corpus.len();
+        let max_df = config.max_df * nb_documents as f64;
+        document_frequencies.retain(|_, df| *df >= config.min_df && *df as f64 <= max_df);
+        if document_frequencies.is_empty() {
+            return Err(format_err!(
+                "Empty vocabulary, the corpus may be empty or the document frequency bounds too \
+                 restrictive"
+            ));
+        }
+        let smoothing = if config.smooth_idf { 1.0 } else { 0.0 };
+        let mut vocabulary = BTreeMap::new();
+        let mut idf = Vec::with_capacity(document_frequencies.len());
+        for (index, (ngram, df)) in document_frequencies.into_iter().enumerate() {
+            let ratio = (nb_documents as f64 + smoothing) / (df as f64 + smoothing);
+            idf.push(ratio.ln() + 1.0);
+            vocabulary.insert(ngram, index);
+        }
+        Ok(TfidfVectorizer {
+            config,
+            language,
+            vocabulary,
+            idf,
+        })
+    }
+
+    /// Load a vectorizer serialized in JSON
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, failure::Error> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .with_context(|_| format!("Cannot open tfidf vectorizer file {:?}", path))?;
+        Self::from_reader(file)
+    }
+
+    /// Load a vectorizer serialized in JSON
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, failure::Error> {
+        let vectorizer: Self = serde_json::from_reader(BufReader::new(reader))
+            .context("Cannot deserialize tfidf vectorizer")?;
+        if vectorizer.idf.len() != vectorizer.vocabulary.len() {
+            return Err(format_err!(
+                "Inconsistent tfidf vectorizer: {} idf values for {} ngrams",
+                vectorizer.idf.len(),
+                vectorizer.vocabulary.len()
+            ));
+        }
+        // Ngram indices must be a permutation of the idf indices
+        let mut is_index_used = vec![false; vectorizer.idf.len()];
+        for (ngram, index) in &vectorizer.vocabulary {
+            match is_index_used.get_mut(*index) {
+                Some(is_used) if !*is_used => *is_used = true,
+                _ => {
+                    return Err(format_err!(
+                        "Inconsistent tfidf vectorizer: invalid or duplicated index {} for ngram \"{}\"",
+                        index,
+                        ngram
+                    ))
+                }
+            }
+        }
+        Ok(vectorizer)
+    }
+
+    /// Serialize the vectorizer in JSON
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<(), failure::Error> {
+        serde_json::to_writer(writer, self).context("Cannot serialize tfidf vectorizer")?;
+        Ok(())
+    }
+
+    pub fn config(&self) -> &TfidfVectorizerConfig {
+        &self.config
+    }
+
+    pub fn vocabulary(&self) -> &BTreeMap<String, usize> {
+        &self.vocabulary
+    }
+
+    /// Inverse document frequencies, indexed by vocabulary index
+    pub fn idf(&self) -> &[f64] {
+        &self.idf
+    }
+
+    /// Compute the TF-IDF vector of the input, ngrams outside of the vocabulary being ignored
+    pub fn transform(&self, input: &str) -> SparseVector {
+        let mut features: FnvHashMap<usize, f64> = FnvHashMap::default();
+        for ngram in ngrams(
+            input,
+            self.language,
+            self.config.max_ngram_size,
+            self.config.normalize_tokens,
+        ) {
+            if let Some(index) = self.vocabulary.get(&ngram) {
+                *features.entry(*index).or_insert(0.0) += 1.0;
+            }
+        }
+        let mut vector = sparse_vector(features);
+        for (index, value) in vector.indices.iter().zip(vector.values.iter_mut()) {
+            if self.config.sublinear_tf {
+                *value = 1.0 + value.ln();
+            }
+            *value *= self.idf[*index];
+        }
+        if self.config.l2_normalize {
+            l2_normalize(&mut vector.values);
+        }
+        vector
+    }
+
+    pub fn transform_all<S: AsRef<str>>(&self, inputs: &[S]) -> CsrMatrix {
+        let rows = inputs
+            .iter()
+            .map(|input| self.transform(input.as_ref()))
+            .collect();
+        CsrMatrix::from_rows(rows, self.vocabulary.len())
+    }
+}
+
+/// Ngrams of the tokens of the input, joined with spaces
+pub(crate) fn ngrams(
+    input: &str,
+    language: Language,
+    max_ngram_size: usize,
+    normalize_tokens: bool,
+) -> Vec<String> {
+    let tokens: Vec<String> = tokenize(input, language)
+        .into_iter()
+        .map(|token| {
+            if normalize_tokens {
+                normalize(&token.value)
+            } else {
+                token.value
+            }
+        })
+        .collect();
+    let tokens: Vec<&str> = tokens.iter().map(|token| &**token).collect();
+    compute_all_ngrams(&tokens, max_ngram_size)
+        .into_iter()
+        .map(|(ngram, _)| ngram)
+        .collect()
+}
+
+/// Build a sparse vector from the features, dropping the null values
+pub(crate) fn sparse_vector(features: FnvHashMap<usize, f64>) -> SparseVector {
+    let mut features: Vec<(usize, f64)> = features
+        .into_iter()
+        .filter(|(_, value)| *value != 0.0)
+        .collect();
+    features.sort_by_key(|(index, _)| *index);
+    let (indices, values) = features.into_iter().unzip();
+    SparseVector { indices, values }
+}
+
+pub(crate) fn l2_normalize(values: &mut [f64]) {
+    let norm = values.iter().map(|value| value * value).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        for value in values.iter_mut() {
+            *value /= norm;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transform_should_hash_ngrams() {
+        // Given
+        let config = HashingVectorizerConfig {
+            max_ngram_size: 2,
+            signed: false,
+            ..Default::default()
+        };
+        let vectorizer = HashingVectorizer::new(config, Language::EN).unwrap();
+
+        // When
+        let vector = vectorizer.transform("Hello World");
+
+        // Then
+        assert_eq!(3, vector.indices.len());
+        assert!(vector.indices.windows(2).all(|w| w[0] < w[1]));
+        let norm: f64 = vector.values.iter().map(|v| v * v).sum();
+        assert!((norm - 1.0).abs() < 1e-9);
+        let expected_index =
+            (i64::from(murmurhash3_32("hello world", 0)).abs() % (1 << 20)) as usize;
+        assert!(vector.indices.contains(&expected_index));
+    }
+
+    #[test]
+    fn tfidf_vectorizer_should_compute_idf() {
+        // Given
+        let corpus = ["a b", "a c", "a b b"];
+        let config = TfidfVectorizerConfig {
+            l2_normalize: false,
+            ..Default::default()
+        };
+
+        // When
+        let vectorizer = TfidfVectorizer::fit(config, &corpus, Language::EN).unwrap();
+        let vector = vectorizer.transform("b b d");
+
+        // Then
+        let expected_idf = vec![1.0, (4.0f64 / 3.0).ln() + 1.0, 2.0f64.ln() + 1.0];
+        assert_eq!(expected_idf, vectorizer.idf());
+        assert_eq!(vec![1], vector.indices);
+        assert_eq!(vec![2.0 * expected_idf[1]], vector.values);
+    }
+
+    #[test]
+    fn tfidf_vectorizer_should_apply_options() {
+        // Given
+        let corpus = ["a b", "a c", "a b b"];
+        let config = TfidfVectorizerConfig {
+            sublinear_tf: true,
+            smooth_idf: false,
+            min_df: 2,
+            max_df: 0.9,
+            l2_normalize: false,
+            ..Default::default()
+        };
+
+        // When
+        let vectorizer = TfidfVectorizer::fit(config, &corpus, Language::EN).unwrap();
+        let vector = vectorizer.transform("b b c");
+
+        // Then
+        assert_eq!(1, vectorizer.vocabulary().len());
+        let idf = 1.5f64.ln() + 1.0;
+        assert_eq!(vec![idf], vectorizer.idf());
+        assert_eq!(vec![(1.0 + 2.0f64.ln()) * idf], vector.values);
+    }
+
+    #[test]
+    fn tfidf_vectorizer_should_fail_on_empty_vocabulary() {
+        let config = TfidfVectorizerConfig {
+            min_df: 2,
+            ..Default::default()
+        };
+        assert!(TfidfVectorizer::fit(config, &["a", "b"], Language::EN).is_err());
+        let empty_corpus: [&str; 0] = [];
+        assert!(TfidfVectorizer::fit(Default::default(), &empty_corpus, Language::EN).is_err());
+    }
+
+    #[test]
+    fn tfidf_vectorizer_should_be_serializable() {
+        // Given
+        let corpus = ["new york city", "new delhi"];
+        let config = TfidfVectorizerConfig {
+            max_ngram_size: 2,
+            ..Default::default()
+        };
+        let vectorizer = TfidfVectorizer::fit(config, &corpus, Language::EN).unwrap();
+
+        // When
+        let mut serialized = vec![];
+        vectorizer.to_writer(&mut serialized).unwrap();
+        let deserialized = TfidfVectorizer::from_reader(&*serialized).unwrap();
+
+        // Then
+        assert_eq!(vectorizer, deserialized);
+        assert_eq!(Some(&4), deserialized.vocabulary().get("new york"));
+        assert!(TfidfVectorizer::from_reader(r#"{"language": "en"}"#.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn tfidf_vectorizer_should_fail_on_invalid_ngram_indices() {
+        // Given
+        let load = |vocabulary: &str, idf: &str| {
+            let json = format!(
+                r#"{{"config": {{}}, "language": "en", "vocabulary": {}, "idf": {}}}"#,
+                vocabulary, idf
+            );
+            TfidfVectorizer::from_reader(json.as_bytes())
+        };
+
+        // Then
+        assert!(load(r#"{"a": 1, "b": 0}"#, "[1.0, 2.0]").is_ok());
+        assert!(load(r#"{"a": 5}"#, "[1.0]").is_err());
+        assert!(load(r#"{"a": 0, "b": 0}"#, "[1.0, 2.0]").is_err());
+    }
+
+    #[test]
+    fn new_should_fail_without_buckets() {
+        // Given
+        let config = HashingVectorizerConfig {
+            nb_buckets: 0,
+            ..Default::default()
+        };
+
+        // When
+        let vectorizer = HashingVectorizer::new(config, Language::EN);
+
+        // Then
+        assert!(vectorizer.is_err());
+    }
+
+    #[test]
+    fn transform_all_should_build_csr_matrix() {
+        // Given
+        let vectorizer =
+            HashingVectorizer::new(HashingVectorizerConfig::default(), Language::EN).unwrap();
+
+        // When
+        let matrix = vectorizer.transform_all(&["foo bar", "", "baz"]);
+
+        // Then
+        assert_eq!(3, matrix.nb_rows());
+        assert_eq!(vec![0, 2, 2, 3], matrix.indptr);
+        assert_eq!(3, matrix.indices.len());
+        assert_eq!(1 << 20, matrix.nb_columns);
+    }
+}
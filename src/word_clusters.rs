@@ -0,0 +1,180 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+use failure::{format_err, ResultExt};
+use fnv::FnvHashMap;
+
+use crate::string::normalize;
+use crate::token::compute_all_ngrams;
+
+/// Mapping from words (or ngrams) to word clusters, such as Brown clusters
+///
+/// Keys are normalized with `normalize` when loaded, so lookups are insensitive to case and
+/// diacritics. Cluster names are interned, which keeps the map compact even when millions of
+/// words share a few thousand clusters.
+#[derive(Debug, Clone, Default)]
+pub struct WordClusters {
+    clusters: Vec<String>,
+    cluster_indexes: FnvHashMap<String, u32>,
+    words: FnvHashMap<String, u32>,
+}
+
+impl WordClusters {
+    /// Load word clusters from a file where each line has the `word\tcluster` format
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, failure::Error> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .with_context(|_| format!("Cannot open word clusters file {:?}", path))?;
+        Self::from_reader(file)
+    }
+
+    /// Load word clusters from a reader where each line has the `word\tcluster` format
+    ///
+    /// Empty lines are ignored. When the same normalized word appears several times, the last
+    /// occurrence wins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use snips_nlu_utils::word_clusters::WordClusters;
+    ///
+    /// let clusters = WordClusters::from_reader("Hello\t0110\nnew york\t0111".as_bytes()).unwrap();
+    /// assert_eq!(Some("0110"), clusters.get("hellö"));
+    /// assert_eq!(Some("0111"), clusters.get_ngram(&["New", "York"]));
+    /// ```
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, failure::Error> {
+        let mut word_clusters = Self::default();
+        for (line_index, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut split = line.split('\t');
+            match (split.next(), split.next(), split.next()) {
+                (Some(word), Some(cluster), None) if !cluster.is_empty() => {
+                    word_clusters.insert(word, cluster)
+                }
+                _ => {
+                    return Err(format_err!(
+                        "Invalid word cluster at line {}: '{}'",
+                        line_index + 1,
+                        line
+                    ))
+                }
+            }
+        }
+        Ok(word_clusters)
+    }
+
+    /// Add a word, or a space separated ngram, to the given cluster
+    pub fn insert(&mut self, word: &str, cluster: &str) {
+        let key = normalize_key(word.split_whitespace());
+        let cluster_index = match self.cluster_indexes.get(cluster) {
+            Some(index) => *index,
+            None => {
+                let index = self.clusters.len() as u32;
+                self.clusters.push(cluster.to_string());
+                self.cluster_indexes.insert(cluster.to_string(), index);
+                index
+            }
+        };
+        self.words.insert(key, cluster_index);
+    }
+
+    /// Return the cluster of the word, after normalization
+    pub fn get(&self, word: &str) -> Option<&str> {
+        self.get_normalized(&normalize_key(word.split_whitespace()))
+    }
+
+    /// Return the cluster of the ngram made of the given tokens, after normalization
+    pub fn get_ngram(&self, tokens: &[&str]) -> Option<&str> {
+        self.get_normalized(&normalize_key(tokens.iter().cloned()))
+    }
+
+    /// Return the cluster of an already normalized word or ngram
+    ///
+    /// This avoids normalizing the input again, e.g. when it comes from
+    /// `Token::normalized_value`.
+    pub fn get_normalized(&self, normalized_word: &str) -> Option<&str> {
+        self.words
+            .get(normalized_word)
+            .map(|index| &*self.clusters[*index as usize])
+    }
+
+    /// Find the clusters of all the ngrams of the tokens, up to `max_ngram_size` tokens
+    ///
+    /// Each result comes with the indexes of the tokens of the matching ngram.
+    pub fn get_all_ngrams(&self, tokens: &[&str], max_ngram_size: usize) -> Vec<(&str, Vec<usize>)> {
+        let normalized_tokens: Vec<String> = tokens.iter().map(|t| normalize(t)).collect();
+        let normalized_refs: Vec<&str> = normalized_tokens.iter().map(|t| &**t).collect();
+        compute_all_ngrams(&normalized_refs, max_ngram_size)
+            .into_iter()
+            .filter_map(|(ngram, indexes)| self.get_normalized(&ngram).map(|c| (c, indexes)))
+            .collect()
+    }
+
+    /// Number of words and ngrams
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Number of distinct clusters
+    pub fn nb_clusters(&self) -> usize {
+        self.clusters.len()
+    }
+}
+
+fn normalize_key<'a, I: Iterator<Item = &'a str>>(words: I) -> String {
+    words
+        .map(normalize)
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_reader_works() {
+        // Given
+        let content = "hello\t0110\nworld\t0111\n\nHéllo World\t0110\n";
+
+        // When
+        let clusters = WordClusters::from_reader(content.as_bytes()).unwrap();
+
+        // Then
+        assert_eq!(3, clusters.len());
+        assert_eq!(2, clusters.nb_clusters());
+        assert_eq!(Some("0110"), clusters.get("HELLO"));
+        assert_eq!(Some("0111"), clusters.get("world"));
+        assert_eq!(Some("0110"), clusters.get("hello   world"));
+        assert_eq!(None, clusters.get("foo"));
+    }
+
+    #[test]
+    fn from_reader_should_fail_on_invalid_line() {
+        let content = "hello\t0110\nworld\n";
+        assert!(WordClusters::from_reader(content.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn get_all_ngrams_works() {
+        // Given
+        let content = "new york\t100\nyork\t101\ncity\t110";
+        let clusters = WordClusters::from_reader(content.as_bytes()).unwrap();
+
+        // When
+        let result = clusters.get_all_ngrams(&["New", "York", "City"], 2);
+
+        // Then
+        let expected = vec![("100", vec![0, 1]), ("101", vec![1]), ("110", vec![2])];
+        assert_eq!(expected, result);
+    }
+}